@@ -0,0 +1,508 @@
+//! A small BibTeX reader.
+//!
+//! Only the subset of BibTeX needed for rendering references is supported:
+//! entries delimited by braces or parentheses, field values in braces or
+//! double quotes, numbers, `@string` macros, month abbreviations and `#`
+//! concatenation. `@comment` and `@preamble` entries are skipped.
+//!
+//! ```rust
+//! use orgize::bibtex::parse;
+//!
+//! let entries = parse(r#"
+//! @string{ acm = "Communications of the ACM" }
+//! @article{knuth1974,
+//!   author = {Donald E. Knuth},
+//!   title = {Computer Programming as an Art},
+//!   journal = acm,
+//!   year = 1974,
+//! }
+//! "#);
+//!
+//! assert_eq!(entries.len(), 1);
+//! assert_eq!(entries[0].key, "knuth1974");
+//! assert_eq!(entries[0].field("Journal"), Some("Communications of the ACM"));
+//! assert_eq!(entries[0].authors()[0].last, "Knuth");
+//! ```
+
+use std::collections::HashMap;
+
+/// BibTeX entry
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct BibEntry {
+    /// Entry type in lowercase, e.g. `article` or `book`
+    pub ty: String,
+    /// Citation key
+    pub key: String,
+    /// Fields with lowercased names, in order of appearance. Braces and
+    /// the most common LaTeX escapes are removed from their values.
+    pub fields: Vec<(String, String)>,
+    /// Names from the `author` field, split before braces are removed, so
+    /// `{Barnes and Noble}` is a single name
+    pub author: Vec<BibName>,
+    /// Names from the `editor` field
+    pub editor: Vec<BibName>,
+}
+
+/// Name of an author or editor
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct BibName {
+    /// Given names, e.g. `Donald E.`
+    pub first: String,
+    /// Family name, including particles like `van`, e.g. `van Rossum`
+    pub last: String,
+}
+
+impl BibEntry {
+    /// Returns the value of a field, the name is case-insensitive.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns authors of this entry, or its editors if it has no authors.
+    pub fn authors(&self) -> &[BibName] {
+        if self.author.is_empty() {
+            &self.editor
+        } else {
+            &self.author
+        }
+    }
+
+    /// Returns the publication year of this entry.
+    pub fn year(&self) -> Option<&str> {
+        self.field("year").or_else(|| {
+            self.field("date")
+                .map(|date| date.split('-').next().unwrap())
+        })
+    }
+}
+
+impl BibName {
+    /// Returns initials of given names, e.g. `D. E.` for `Donald E.`.
+    pub fn initials(&self) -> String {
+        self.first
+            .split(|c: char| c.is_whitespace() || c == '.')
+            .filter_map(|name| name.chars().next())
+            .map(|c| format!("{}.", c))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Parses BibTeX entries from a string.
+///
+/// Malformed entries are skipped.
+pub fn parse(input: &str) -> Vec<BibEntry> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        macros: HashMap::new(),
+    };
+    let mut entries = Vec::new();
+
+    while let Some(i) = input[parser.pos..].find('@') {
+        parser.pos += i + 1;
+        let start = parser.pos;
+        match parser.entry() {
+            Some(entry) => entries.extend(entry),
+            // resume right after the `@` of the malformed entry
+            None => parser.pos = start,
+        }
+    }
+
+    entries
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    macros: HashMap<String, String>,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "{}()=,#\"".contains(c))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(&rest[0..len]).filter(|ident| !ident.is_empty())
+    }
+
+    // returns `None` if the entry is malformed, or `Some(None)` for entries
+    // which are not references
+    fn entry(&mut self) -> Option<Option<BibEntry>> {
+        let ty = self.identifier()?.to_ascii_lowercase();
+        self.skip_whitespace();
+        let close = match self.peek()? {
+            '{' => '}',
+            '(' => ')',
+            _ => return None,
+        };
+
+        match &*ty {
+            "comment" | "preamble" => {
+                self.balanced()?;
+                Some(None)
+            }
+            "string" => {
+                self.pos += 1;
+                let name = self.identifier()?.to_ascii_lowercase();
+                if !self.eat('=') {
+                    return None;
+                }
+                let value = self.value()?;
+                self.macros.insert(name, value);
+                if !self.eat(close) {
+                    return None;
+                }
+                Some(None)
+            }
+            _ => {
+                self.pos += 1;
+                let key = self.identifier()?.to_string();
+                let mut fields = Vec::new();
+                let mut author = Vec::new();
+                let mut editor = Vec::new();
+                loop {
+                    if self.eat(close) {
+                        break;
+                    }
+                    if !self.eat(',') {
+                        return None;
+                    }
+                    if self.eat(close) {
+                        break;
+                    }
+                    let name = self.identifier()?.to_ascii_lowercase();
+                    if !self.eat('=') {
+                        return None;
+                    }
+                    let value = self.value()?;
+                    match &*name {
+                        "author" => author = parse_names(&value),
+                        "editor" => editor = parse_names(&value),
+                        _ => (),
+                    }
+                    fields.push((name, clean(&value)));
+                }
+                Some(Some(BibEntry {
+                    ty,
+                    key,
+                    fields,
+                    author,
+                    editor,
+                }))
+            }
+        }
+    }
+
+    // raw field value, with concatenated parts and expanded macros
+    fn value(&mut self) -> Option<String> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                '{' => {
+                    let part = self.balanced()?;
+                    value.push_str(&part[1..part.len() - 1]);
+                }
+                '"' => {
+                    let rest = &self.input[self.pos + 1..];
+                    let mut depth = 0;
+                    let len = rest.char_indices().find_map(|(i, c)| {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            '"' if depth == 0 => return Some(i),
+                            _ => (),
+                        }
+                        None
+                    })?;
+                    value.push_str(&rest[0..len]);
+                    self.pos += len + 2;
+                }
+                _ => {
+                    let ident = self.identifier()?;
+                    if ident.bytes().all(|c| c.is_ascii_digit()) {
+                        value.push_str(ident);
+                    } else {
+                        let ident = ident.to_ascii_lowercase();
+                        if let Some(expansion) = self.macros.get(&ident) {
+                            value.push_str(expansion);
+                        } else if let Some(month) = month(&ident) {
+                            value.push_str(month);
+                        }
+                    }
+                }
+            }
+            if !self.eat('#') {
+                break;
+            }
+        }
+        Some(value)
+    }
+
+    // text enclosed in balanced braces or parentheses, including delimiters
+    fn balanced(&mut self) -> Option<&'a str> {
+        let rest = &self.input[self.pos..];
+        let (open, close) = match rest.chars().next()? {
+            '{' => ('{', '}'),
+            _ => ('(', ')'),
+        };
+        let mut depth = 0;
+        for (i, c) in rest.char_indices() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    self.pos += i + 1;
+                    return Some(&rest[0..=i]);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn month(name: &str) -> Option<&'static str> {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    MONTHS
+        .iter()
+        .find(|month| name.len() == 3 && month[0..3].eq_ignore_ascii_case(name))
+        .copied()
+}
+
+// removes braces and common LaTeX escapes, and collapses whitespaces
+fn clean(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => (),
+            '~' => output.push(' '),
+            '\\' => match chars.peek() {
+                // escaped special characters, e.g. `\&`
+                Some(&c) if "&%$#_{}".contains(c) => {
+                    output.push(c);
+                    chars.next();
+                }
+                // accents, e.g. `\"o`, just keep the letter
+                Some(&c) if "'`^\"~=.".contains(c) => {
+                    chars.next();
+                }
+                // commands, e.g. `\emph`, are dropped
+                _ => {
+                    while chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                        chars.next();
+                    }
+                }
+            },
+            c => output.push(c),
+        }
+    }
+
+    output.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// splits a raw list of names, like `Knuth, Donald and Guido van Rossum`,
+// `and` inside braces doesn't separate names
+fn parse_names(names: &str) -> Vec<BibName> {
+    let words: Vec<_> = split_outside_braces(names, is_space)
+        .into_iter()
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words
+        .split(|word| word.eq_ignore_ascii_case("and"))
+        .filter(|name| !name.is_empty())
+        .map(|name| parse_name(&name.join(" ")))
+        .collect()
+}
+
+fn parse_name(name: &str) -> BibName {
+    if let Some((last, first)) = split_outside_braces(name, |c| c == ',').split_first() {
+        if !first.is_empty() {
+            return BibName {
+                first: clean(&first.join(",")),
+                last: clean(last),
+            };
+        }
+    }
+
+    let words: Vec<_> = split_outside_braces(name, is_space)
+        .into_iter()
+        .filter(|word| !word.is_empty())
+        .map(clean)
+        .collect();
+    // family name starts from the first lowercase particle, or is the last
+    // word
+    let start = words
+        .iter()
+        .position(|word| word.starts_with(char::is_lowercase))
+        .filter(|&i| i > 0)
+        .unwrap_or(words.len() - 1);
+    BibName {
+        first: words[0..start].join(" "),
+        last: words[start..].join(" "),
+    }
+}
+
+fn is_space(c: char) -> bool {
+    c.is_whitespace() || c == '~'
+}
+
+// splits `value` at separators outside braces
+fn split_outside_braces(value: &str, is_separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            c if depth == 0 && is_separator(c) => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+#[test]
+fn parse_entries() {
+    let entries = parse(
+        r#"
+% a comment
+@comment{ ignored @misc{no, title = "No"} }
+@String(pub = {O'Reilly})
+@Book{doe2020,
+  Author    = "Doe, John and Jane {van} Smith",
+  title     = {The {GPU} Book \& Other {S}tories},
+  publisher = pub # " Media",
+  year      = 2020,
+  month     = mar,
+}
+@misc{broken, title = }
+@online(web, title = {W\"orld~Wide}, date = {2019-04-01})
+"#,
+    );
+
+    assert_eq!(
+        entries,
+        vec![
+            BibEntry {
+                ty: "book".into(),
+                key: "doe2020".into(),
+                fields: vec![
+                    ("author".into(), "Doe, John and Jane van Smith".into()),
+                    ("title".into(), "The GPU Book & Other Stories".into()),
+                    ("publisher".into(), "O'Reilly Media".into()),
+                    ("year".into(), "2020".into()),
+                    ("month".into(), "March".into()),
+                ],
+                author: vec![
+                    BibName {
+                        first: "John".into(),
+                        last: "Doe".into()
+                    },
+                    BibName {
+                        first: "Jane".into(),
+                        last: "van Smith".into()
+                    },
+                ],
+                editor: vec![],
+            },
+            BibEntry {
+                ty: "online".into(),
+                key: "web".into(),
+                fields: vec![
+                    ("title".into(), "World Wide".into()),
+                    ("date".into(), "2019-04-01".into()),
+                ],
+                author: vec![],
+                editor: vec![],
+            },
+        ]
+    );
+
+    assert_eq!(entries[1].year(), Some("2019"));
+    assert_eq!(
+        entries[0].authors(),
+        vec![
+            BibName {
+                first: "John".into(),
+                last: "Doe".into()
+            },
+            BibName {
+                first: "Jane".into(),
+                last: "van Smith".into()
+            },
+        ]
+    );
+    assert_eq!(
+        parse_names("Donald E. Knuth")[0].initials(),
+        "D. E.".to_string()
+    );
+    assert_eq!(
+        parse(r#"@book{bn, editor = {{Barnes and Noble} and Knuth, D.~E.}}"#)[0].authors(),
+        &[
+            BibName {
+                first: "".into(),
+                last: "Barnes and Noble".into()
+            },
+            BibName {
+                first: "D. E.".into(),
+                last: "Knuth".into()
+            },
+        ]
+    );
+}
@@ -3,12 +3,67 @@
 pub struct ParseConfig {
     /// Headline's todo keywords
     pub todo_keywords: (Vec<String>, Vec<String>),
+    /// Minimum level of inlinetasks, headlines with at least this many stars
+    /// are parsed as inlinetasks. `None` disables inlinetasks.
+    pub inlinetask_min_level: Option<usize>,
+    /// Number of columns a tab character advances to, used when comparing
+    /// indentation of list items
+    pub tab_width: usize,
+    /// Whether and how subscripts and superscripts are parsed, like
+    /// `#+OPTIONS: ^:t`, `^:{}` and `^:nil`
+    pub use_sub_superscripts: UseSubSuperscripts,
+    /// Link types recognized in plain links and angle links, e.g. `https`
+    /// in `https://example.com` and `<https://example.com>`
+    pub link_types: Vec<String>,
+}
+
+/// How subscripts and superscripts are recognized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UseSubSuperscripts {
+    /// `a_b`, `a^b` and `a_{b}` are all parsed, like `^:t`
+    Always,
+    /// Only `a_{b}` and `a^{b}` are parsed, like `^:{}`
+    Braces,
+    /// Never parsed, like `^:nil`
+    Never,
 }
 
 impl Default for ParseConfig {
     fn default() -> Self {
         ParseConfig {
             todo_keywords: (vec![String::from("TODO")], vec![String::from("DONE")]),
+            inlinetask_min_level: Some(15),
+            tab_width: 8,
+            use_sub_superscripts: UseSubSuperscripts::Always,
+            link_types: [
+                "attachment",
+                "bbdb",
+                "bibtex",
+                "docview",
+                "doi",
+                "elisp",
+                "eww",
+                "file",
+                "file+emacs",
+                "file+sys",
+                "ftp",
+                "gnus",
+                "help",
+                "http",
+                "https",
+                "id",
+                "info",
+                "irc",
+                "mailto",
+                "mhe",
+                "news",
+                "rmail",
+                "shell",
+                "w3m",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
         }
     }
 }
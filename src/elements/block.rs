@@ -7,7 +7,7 @@ use nom::{
     IResult,
 };
 
-use crate::elements::Element;
+use crate::elements::{AffiliatedKeywords, Element, HeaderArgs};
 use crate::parse::combinators::{blank_lines_count, line, lines_till};
 
 /// Special Block Element
@@ -26,6 +26,9 @@ pub struct SpecialBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl SpecialBlock<'_> {
@@ -35,6 +38,7 @@ impl SpecialBlock<'_> {
             parameters: self.parameters.map(Into::into).map(Cow::Owned),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -53,6 +57,9 @@ pub struct QuoteBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl QuoteBlock<'_> {
@@ -61,6 +68,7 @@ impl QuoteBlock<'_> {
             parameters: self.parameters.map(Into::into).map(Cow::Owned),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -79,6 +87,9 @@ pub struct CenterBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl CenterBlock<'_> {
@@ -87,6 +98,7 @@ impl CenterBlock<'_> {
             parameters: self.parameters.map(Into::into).map(Cow::Owned),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -105,6 +117,9 @@ pub struct VerseBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl VerseBlock<'_> {
@@ -113,6 +128,7 @@ impl VerseBlock<'_> {
             parameters: self.parameters.map(Into::into).map(Cow::Owned),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -129,6 +145,9 @@ pub struct CommentBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl CommentBlock<'_> {
@@ -137,6 +156,7 @@ impl CommentBlock<'_> {
             data: self.data.map(Into::into).map(Cow::Owned),
             contents: self.contents.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -153,14 +173,29 @@ pub struct ExampleBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl ExampleBlock<'_> {
+    /// Returns block contents, with escape commas before `*` and `#+`
+    /// removed.
+    pub fn unescaped_contents(&self) -> Cow<'_, str> {
+        unescape(&self.contents)
+    }
+
+    /// Parses switches in block data.
+    pub fn switches(&self) -> BlockSwitches<'_> {
+        BlockSwitches::parse(self.data.as_deref().unwrap_or_default()).0
+    }
+
     pub fn into_owned(self) -> ExampleBlock<'static> {
         ExampleBlock {
             data: self.data.map(Into::into).map(Cow::Owned),
             contents: self.contents.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -176,6 +211,9 @@ pub struct ExportBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl ExportBlock<'_> {
@@ -184,6 +222,7 @@ impl ExportBlock<'_> {
             data: self.data.into_owned().into(),
             contents: self.contents.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -201,6 +240,9 @@ pub struct SourceBlock<'a> {
     /// Numbers of blank lines between last block's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl SourceBlock<'_> {
@@ -210,14 +252,198 @@ impl SourceBlock<'_> {
             arguments: self.arguments.into_owned().into(),
             contents: self.contents.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
+
+    /// Returns block contents, with escape commas before `*` and `#+`
+    /// removed.
+    pub fn unescaped_contents(&self) -> Cow<'_, str> {
+        unescape(&self.contents)
+    }
+
+    /// Parses switches at the beginning of block arguments, before any
+    /// header argument.
+    pub fn switches(&self) -> BlockSwitches<'_> {
+        BlockSwitches::parse(&self.arguments).0
+    }
+
+    /// Parses header arguments after the switches, followed by the ones
+    /// from `#+HEADER:` keywords, which take precedence.
+    ///
+    /// Inherited header arguments are not included, see
+    /// [`Org::header_args`](crate::Org::header_args).
+    pub fn header_args(&self) -> HeaderArgs<'_> {
+        let mut args = HeaderArgs::parse(BlockSwitches::parse(&self.arguments).1);
+        for header in self.affiliated.iter().flat_map(|a| &a.header) {
+            args.extend(HeaderArgs::parse(header));
+        }
+        args
+    }
+}
+
+/// Switches of example and source blocks
+///
+/// ```text
+/// #+BEGIN_SRC emacs-lisp -n 10 -r -l "((%s))"
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct BlockSwitches<'a> {
+    /// Line numbering, from `-n` or `+n` switch
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub number_lines: Option<NumberLines>,
+    /// Whether to keep the indentation of block contents, `-i` switch
+    pub preserve_indent: bool,
+    /// Whether to keep coderef labels in exported code, unset by `-r`
+    /// switch unless `-k` is present with line numbering
+    pub retain_labels: bool,
+    /// Whether links to coderefs use labels instead of line numbers, unset
+    /// by `-r` or `-k` switch
+    pub use_labels: bool,
+    /// Format of coderef labels, from `-l` switch, defaults to `(ref:%s)`
+    pub label_fmt: Cow<'a, str>,
+}
+
+/// Line numbering of example and source blocks
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(tag = "type", content = "start"))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+pub enum NumberLines {
+    /// `-n N`, numbering starts from `N`, which defaults to 1
+    New(usize),
+    /// `+n N`, numbering continues from previous numbered block, first line
+    /// is numbered its last number plus `N`, which defaults to 1
+    Continued(usize),
+}
+
+impl Default for BlockSwitches<'_> {
+    fn default() -> Self {
+        BlockSwitches {
+            number_lines: None,
+            preserve_indent: false,
+            retain_labels: true,
+            use_labels: true,
+            label_fmt: Cow::Borrowed("(ref:%s)"),
+        }
+    }
+}
+
+impl<'a> BlockSwitches<'a> {
+    /// Parses switches from the beginning of `input`, stops at the first
+    /// header argument (starting with `:`). Returns parsed switches and
+    /// the remaining input.
+    pub fn parse(input: &'a str) -> (BlockSwitches<'a>, &'a str) {
+        let mut switches = BlockSwitches::default();
+        let mut remove_labels = false;
+        let mut keep_labels = false;
+        let mut input = input.trim_start();
+
+        while !input.is_empty() && !input.starts_with(':') {
+            let end = input.find(char::is_whitespace).unwrap_or(input.len());
+            let (switch, tail) = input.split_at(end);
+            let mut tail = tail.trim_start();
+
+            match switch {
+                "-n" | "+n" => {
+                    let digits = tail
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(tail.len());
+                    let start = tail[0..digits].parse().unwrap_or(1);
+                    if digits > 0 {
+                        tail = tail[digits..].trim_start();
+                    }
+                    switches.number_lines = Some(if switch == "-n" {
+                        NumberLines::New(start)
+                    } else {
+                        NumberLines::Continued(start)
+                    });
+                }
+                "-i" => switches.preserve_indent = true,
+                "-r" => remove_labels = true,
+                "-k" => keep_labels = true,
+                "-l" if tail.starts_with('"') => {
+                    if let Some(len) = tail[1..].find(&['"', '\n'][..]) {
+                        if tail.as_bytes()[len + 1] == b'"' {
+                            switches.label_fmt = tail[1..=len].into();
+                            tail = tail[len + 2..].trim_start();
+                        }
+                    }
+                }
+                _ => (),
+            }
+
+            input = tail;
         }
+
+        switches.retain_labels = !remove_labels || (keep_labels && switches.number_lines.is_some());
+        switches.use_labels = switches.retain_labels && !keep_labels;
+
+        (switches, input)
     }
 
-    // TODO: fn number_lines() -> Some(New) | Some(Continued) | None {  }
-    // TODO: fn preserve_indent() -> bool {  }
-    // TODO: fn use_labels() -> bool {  }
-    // TODO: fn label_fmt() -> Option<String> {  }
-    // TODO: fn retain_labels() -> bool {  }
+    /// Splits a coderef label from the end of `line`, returns the code
+    /// before the label and the label name.
+    pub fn split_label<'b>(&self, line: &'b str) -> Option<(&'b str, &'b str)> {
+        let mut parts = self.label_fmt.splitn(2, "%s");
+        let (prefix, suffix) = (parts.next()?, parts.next()?);
+        if prefix.is_empty() {
+            return None;
+        }
+
+        let code = line.trim_end().strip_suffix(suffix)?;
+        let start = code.rfind(prefix)?;
+        let label = &code[start + prefix.len()..];
+        let code = &code[0..start];
+
+        let valid = label.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ' ')
+            && (code.is_empty() || code.ends_with(&[' ', '\t'][..]));
+
+        if valid {
+            Some((code, label))
+        } else {
+            None
+        }
+    }
+}
+
+/// Removes escape commas from code, i.e. the last comma of leading commas
+/// before `*` or `#+`.
+pub(crate) fn unescape(contents: &str) -> Cow<'_, str> {
+    if !contents.lines().any(|line| escape_comma(line).is_some()) {
+        return Cow::Borrowed(contents);
+    }
+
+    let mut output = String::with_capacity(contents.len());
+    for line in contents.split_inclusive('\n') {
+        match escape_comma(line) {
+            Some(i) => {
+                output.push_str(&line[0..i]);
+                output.push_str(&line[i + 1..]);
+            }
+            None => output.push_str(line),
+        }
+    }
+    Cow::Owned(output)
+}
+
+// byte position of the escape comma in line
+fn escape_comma(line: &str) -> Option<usize> {
+    let indent = line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+    let commas = line[indent..].len() - line[indent..].trim_start_matches(',').len();
+    let rest = &line[indent + commas..];
+
+    if commas > 0 && (rest.starts_with('*') || rest.starts_with("#+")) {
+        Some(indent + commas - 1)
+    } else {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -259,36 +485,42 @@ impl<'a> RawBlock<'a> {
                 parameters: arguments,
                 pre_blank,
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "QUOTE" => QuoteBlock {
                 parameters: arguments,
                 pre_blank,
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "VERSE" => VerseBlock {
                 parameters: arguments,
                 pre_blank,
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "COMMENT" => CommentBlock {
                 data: arguments,
                 contents: contents.into(),
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "EXAMPLE" => ExampleBlock {
                 data: arguments,
                 contents: contents.into(),
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "EXPORT" => ExportBlock {
                 data: arguments.unwrap_or_default(),
                 contents: contents.into(),
                 post_blank,
+                affiliated: None,
             }
             .into(),
             "SRC" => {
@@ -308,6 +540,7 @@ impl<'a> RawBlock<'a> {
                     language,
                     contents: contents.into(),
                     post_blank,
+                    affiliated: None,
                 }
                 .into()
             }
@@ -316,6 +549,7 @@ impl<'a> RawBlock<'a> {
                 name: name.into(),
                 pre_blank,
                 post_blank,
+                affiliated: None,
             }
             .into(),
         };
@@ -406,3 +640,60 @@ console.log('Hello World!');
     );
     // TODO: more testing
 }
+
+#[test]
+fn unescape_contents() {
+    assert_eq!(unescape("a\nb\n"), Cow::Borrowed("a\nb\n"));
+    assert_eq!(
+        unescape(",* a\n  ,#+b\n,,* c\n,a\n, * d\n#+e"),
+        Cow::<str>::Owned("* a\n  #+b\n,* c\n,a\n, * d\n#+e".into())
+    );
+}
+
+#[test]
+fn parse_switches() {
+    assert_eq!(BlockSwitches::parse(""), (BlockSwitches::default(), ""));
+    assert_eq!(
+        BlockSwitches::parse(" -n 10 -i -l \"((%s))\" :var x=1"),
+        (
+            BlockSwitches {
+                number_lines: Some(NumberLines::New(10)),
+                preserve_indent: true,
+                retain_labels: true,
+                use_labels: true,
+                label_fmt: "((%s))".into(),
+            },
+            ":var x=1"
+        )
+    );
+    assert_eq!(
+        BlockSwitches::parse("+n -r"),
+        (
+            BlockSwitches {
+                number_lines: Some(NumberLines::Continued(1)),
+                retain_labels: false,
+                use_labels: false,
+                ..Default::default()
+            },
+            ""
+        )
+    );
+    assert_eq!(
+        BlockSwitches::parse("-n -r -k").0,
+        BlockSwitches {
+            number_lines: Some(NumberLines::New(1)),
+            retain_labels: true,
+            use_labels: false,
+            ..Default::default()
+        }
+    );
+
+    let switches = BlockSwitches::default();
+    assert_eq!(
+        switches.split_label("(save-excursion  (ref:sc)"),
+        Some(("(save-excursion  ", "sc"))
+    );
+    assert_eq!(switches.split_label("(ref:jump)\n"), Some(("", "jump")));
+    assert_eq!(switches.split_label("x(ref:sc)"), None);
+    assert_eq!(switches.split_label("x (ref:)"), None);
+}
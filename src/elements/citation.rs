@@ -0,0 +1,217 @@
+use std::borrow::Cow;
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::opt,
+    sequence::preceded,
+    Err, IResult,
+};
+
+/// Citation Object
+///
+/// # Syntax
+///
+/// ```text
+/// [cite/STYLE:GLOBALPREFIX;REFERENCES;GLOBALSUFFIX]
+/// ```
+///
+/// `REFERENCES` are separated by semicolons, each of them looks like
+/// `KEYPREFIX @KEY KEYSUFFIX`. Prefixes and suffixes are kept as-is,
+/// including their surrounding whitespaces.
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct Citation<'a> {
+    /// Citation style and its variant, e.g. `t` or `text/c`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub style: Option<Cow<'a, str>>,
+    /// Common prefix of all references
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub prefix: Option<Cow<'a, str>>,
+    /// Common suffix of all references
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub suffix: Option<Cow<'a, str>>,
+    /// Cited references, at least one
+    pub references: Vec<CitationReference<'a>>,
+}
+
+/// Reference inside a citation
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct CitationReference<'a> {
+    /// Citation key, without the leading `@`
+    pub key: Cow<'a, str>,
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub prefix: Option<Cow<'a, str>>,
+    /// Suffix of this reference, usually a locator like `p. 3`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub suffix: Option<Cow<'a, str>>,
+}
+
+impl Citation<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, Citation<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    pub fn into_owned(self) -> Citation<'static> {
+        Citation {
+            style: self.style.map(Into::into).map(Cow::Owned),
+            prefix: self.prefix.map(Into::into).map(Cow::Owned),
+            suffix: self.suffix.map(Into::into).map(Cow::Owned),
+            references: self
+                .references
+                .into_iter()
+                .map(CitationReference::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl CitationReference<'_> {
+    /// Returns the suffix of this reference without leading comma and
+    /// whitespaces, e.g. `p. 3` for `@key, p. 3`, or `None` if it's empty.
+    pub fn locator(&self) -> Option<&str> {
+        self.suffix
+            .as_deref()
+            .map(|suffix| suffix.trim_start_matches(|c: char| c == ',' || c.is_whitespace()))
+            .map(str::trim_end)
+            .filter(|locator| !locator.is_empty())
+    }
+
+    pub fn into_owned(self) -> CitationReference<'static> {
+        CitationReference {
+            key: self.key.into_owned().into(),
+            prefix: self.prefix.map(Into::into).map(Cow::Owned),
+            suffix: self.suffix.map(Into::into).map(Cow::Owned),
+        }
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || "-.:?!`'/*@+|(){}<>&_^$#%~".contains(c)
+}
+
+fn non_empty(s: &str) -> Option<Cow<'_, str>> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.into())
+    }
+}
+
+// parses `KEYPREFIX @KEY KEYSUFFIX`
+fn parse_reference(input: &str) -> Option<CitationReference<'_>> {
+    let at = input.find('@')?;
+    let key_len = input[at + 1..]
+        .find(|c: char| !is_key_char(c))
+        .unwrap_or(input.len() - at - 1);
+    if key_len == 0 {
+        return None;
+    }
+
+    Some(CitationReference {
+        prefix: non_empty(&input[0..at]),
+        key: input[at + 1..at + 1 + key_len].into(),
+        suffix: non_empty(&input[at + 1 + key_len..]),
+    })
+}
+
+#[inline]
+fn parse_internal(input: &str) -> IResult<&str, Citation<'_>, ()> {
+    let (input, _) = tag("[cite")(input)?;
+    let (input, style) = opt(preceded(
+        tag("/"),
+        take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '/'),
+    ))(input)?;
+    let (input, _) = tag(":")(input)?;
+
+    let end = input.find(']').ok_or(Err::Error(()))?;
+    let contents = &input[0..end];
+    if contents.contains('[') || contents.contains("\n\n") {
+        return Err(Err::Error(()));
+    }
+
+    let parts: Vec<_> = contents.split(';').collect();
+    let first = parts
+        .iter()
+        .position(|part| parse_reference(part).is_some());
+    let last = parts
+        .iter()
+        .rposition(|part| parse_reference(part).is_some());
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) if first <= 1 && last + 2 >= parts.len() => (first, last),
+        _ => return Err(Err::Error(())),
+    };
+
+    let references = parts[first..=last]
+        .iter()
+        .map(|part| parse_reference(part))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Err::Error(()))?;
+
+    Ok((
+        &input[end + 1..],
+        Citation {
+            style: style.map(Into::into),
+            prefix: parts[0..first].first().and_then(|prefix| non_empty(prefix)),
+            suffix: parts[last + 1..]
+                .first()
+                .and_then(|suffix| non_empty(suffix)),
+            references,
+        },
+    ))
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Citation::parse("[cite:@key]"),
+        Some((
+            "",
+            Citation {
+                style: None,
+                prefix: None,
+                suffix: None,
+                references: vec![CitationReference {
+                    key: "key".into(),
+                    prefix: None,
+                    suffix: None,
+                }],
+            }
+        ))
+    );
+    assert_eq!(
+        Citation::parse("[cite/t/c:see ;@doe2020 p. 3; also @smith:1999, ch. 2;  and others] x"),
+        Some((
+            " x",
+            Citation {
+                style: Some("t/c".into()),
+                prefix: Some("see ".into()),
+                suffix: Some("  and others".into()),
+                references: vec![
+                    CitationReference {
+                        key: "doe2020".into(),
+                        prefix: None,
+                        suffix: Some(" p. 3".into()),
+                    },
+                    CitationReference {
+                        key: "smith:1999".into(),
+                        prefix: Some(" also ".into()),
+                        suffix: Some(", ch. 2".into()),
+                    },
+                ],
+            }
+        ))
+    );
+    assert_eq!(
+        Citation::parse("[cite:@a, p. 3 ]")
+            .and_then(|(_, citation)| citation.references[0].locator().map(String::from)),
+        Some("p. 3".into())
+    );
+    assert!(Citation::parse("[cite:no key]").is_none());
+    assert!(Citation::parse("[cite:@a;middle;mid;@b]").is_none());
+    assert!(Citation::parse("[cite:@]").is_none());
+    assert!(Citation::parse("[cite @a]").is_none());
+    assert!(Citation::parse("[cite:@a").is_none());
+}
@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-use crate::elements::timestamp::{parse_inactive, Datetime, Timestamp};
+use crate::elements::timestamp::{parse_inactive, Datetime, Delay, Repeater, Timestamp};
 use crate::parse::combinators::{blank_lines_count, eol};
 
 /// Clock Element
@@ -24,11 +24,14 @@ pub enum Clock<'a> {
         /// Time end
         end: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
         /// Clock duration
         duration: Cow<'a, str>,
+        /// Whether the clock is written as a time range inside a single
+        /// timestamp, e.g. `[2003-09-16 Tue 09:39-10:39]`
+        time_range: bool,
         /// Numbers of blank lines between the clock line and next non-blank
         /// line or buffer's end
         post_blank: usize,
@@ -38,9 +41,9 @@ pub enum Clock<'a> {
         /// Time start
         start: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
         /// Numbers of blank lines between the clock line and next non-blank
         /// line or buffer's end
         post_blank: usize,
@@ -60,13 +63,15 @@ impl Clock<'_> {
                 repeater,
                 delay,
                 duration,
+                time_range,
                 post_blank,
             } => Clock::Closed {
                 start: start.into_owned(),
                 end: end.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
                 duration: duration.into_owned().into(),
+                time_range,
                 post_blank,
             },
             Clock::Running {
@@ -76,8 +81,8 @@ impl Clock<'_> {
                 post_blank,
             } => Clock::Running {
                 start: start.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
                 post_blank,
             },
         }
@@ -110,6 +115,19 @@ impl Clock<'_> {
     /// Constructs a timestamp from the clock.
     pub fn value(&self) -> Timestamp {
         match &*self {
+            Clock::Closed {
+                start,
+                end,
+                repeater,
+                delay,
+                time_range: true,
+                ..
+            } => Timestamp::InactiveTimeRange {
+                start: start.clone(),
+                end: end.clone(),
+                repeater: *repeater,
+                delay: *delay,
+            },
             Clock::Closed {
                 start,
                 end,
@@ -119,8 +137,8 @@ impl Clock<'_> {
             } => Timestamp::InactiveRange {
                 start: start.clone(),
                 end: end.clone(),
-                repeater: repeater.clone(),
-                delay: delay.clone(),
+                repeater: *repeater,
+                delay: *delay,
             },
             Clock::Running {
                 start,
@@ -129,8 +147,8 @@ impl Clock<'_> {
                 ..
             } => Timestamp::Inactive {
                 start: start.clone(),
-                repeater: repeater.clone(),
-                delay: delay.clone(),
+                repeater: *repeater,
+                delay: *delay,
             },
         }
     }
@@ -141,6 +159,7 @@ fn parse_internal(input: &str) -> IResult<&str, Clock, ()> {
     let (input, _) = tag("CLOCK:")(input)?;
     let (input, _) = space0(input)?;
     let (input, timestamp) = parse_inactive(input)?;
+    let time_range = matches!(timestamp, Timestamp::InactiveTimeRange { .. });
 
     match timestamp {
         Timestamp::InactiveRange {
@@ -148,6 +167,13 @@ fn parse_internal(input: &str) -> IResult<&str, Clock, ()> {
             end,
             repeater,
             delay,
+        }
+        // time range inside a single day, its end has the same date
+        | Timestamp::InactiveTimeRange {
+            start,
+            end,
+            repeater,
+            delay,
         } => {
             let (input, _) = space0(input)?;
             let (input, _) = tag("=>")(input)?;
@@ -163,6 +189,7 @@ fn parse_internal(input: &str) -> IResult<&str, Clock, ()> {
                     repeater,
                     delay,
                     duration: duration.into(),
+                    time_range,
                     post_blank: blank,
                 },
             ))
@@ -185,7 +212,8 @@ fn parse_internal(input: &str) -> IResult<&str, Clock, ()> {
             ))
         }
         _ => unreachable!(
-            "`parse_inactive` only returns `Timestamp::InactiveRange` or `Timestamp::Inactive`."
+            "`parse_inactive` only returns `Timestamp::InactiveRange`, \
+             `Timestamp::InactiveTimeRange` or `Timestamp::Inactive`."
         ),
     }
 }
@@ -235,8 +263,38 @@ fn parse() {
                 repeater: None,
                 delay: None,
                 duration: "1:00".into(),
+                time_range: false,
                 post_blank: 1,
             }
         ))
     );
+    assert_eq!(
+        Clock::parse("CLOCK: [2003-09-16 Tue 09:39-10:39] =>  1:00"),
+        Some((
+            "",
+            Clock::Closed {
+                start: Datetime {
+                    year: 2003,
+                    month: 9,
+                    day: 16,
+                    dayname: "Tue".into(),
+                    hour: Some(9),
+                    minute: Some(39)
+                },
+                end: Datetime {
+                    year: 2003,
+                    month: 9,
+                    day: 16,
+                    dayname: "Tue".into(),
+                    hour: Some(10),
+                    minute: Some(39)
+                },
+                repeater: None,
+                delay: None,
+                duration: "1:00".into(),
+                time_range: true,
+                post_blank: 0,
+            }
+        ))
+    );
 }
@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use nom::{bytes::complete::tag, IResult};
+
+use crate::elements::AffiliatedKeywords;
+use crate::parse::combinators::{blank_lines_count, line};
+
+/// Diary Sexp Element
+///
+/// # Syntax
+///
+/// ```text
+/// %%(VALUE
+/// ```
+///
+/// A diary sexp must start at the beginning of a line.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct DiarySexp<'a> {
+    /// Sexp value, without the leading `%%`, e.g.
+    /// `(diary-anniversary 10 31 1948)`
+    pub value: Cow<'a, str>,
+    /// Numbers of blank lines between diary sexp line and next non-blank
+    /// line or buffer's end
+    pub post_blank: usize,
+    /// Affiliated keywords attached to this diary sexp
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
+}
+
+impl DiarySexp<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, DiarySexp<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    pub fn into_owned(self) -> DiarySexp<'static> {
+        DiarySexp {
+            value: self.value.into_owned().into(),
+            post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
+}
+
+fn parse_internal(input: &str) -> IResult<&str, DiarySexp<'_>, ()> {
+    let (tail, _) = tag("%%")(input)?;
+    let (_, _) = tag("(")(tail)?;
+    let (tail, value) = line(tail)?;
+    let (tail, post_blank) = blank_lines_count(tail)?;
+
+    Ok((
+        tail,
+        DiarySexp {
+            value: value.trim_end().into(),
+            post_blank,
+            affiliated: None,
+        },
+    ))
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        DiarySexp::parse("%%(diary-anniversary 10 31 1948) Arthur's birthday\n\ntext"),
+        Some((
+            "text",
+            DiarySexp {
+                value: "(diary-anniversary 10 31 1948) Arthur's birthday".into(),
+                post_blank: 1,
+                affiliated: None,
+            }
+        ))
+    );
+    assert_eq!(
+        DiarySexp::parse("%%(org-calendar-holiday)").map(|(_, sexp)| sexp.value),
+        Some("(org-calendar-holiday)".into())
+    );
+    assert!(DiarySexp::parse("  %%(diary-float t 4 2)").is_none());
+    assert!(DiarySexp::parse("%% (diary-float t 4 2)").is_none());
+    assert!(DiarySexp::parse("%%").is_none());
+}
@@ -7,6 +7,7 @@ use nom::{
     IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, eol, lines_till};
 
 /// Drawer Element
@@ -22,6 +23,9 @@ pub struct Drawer<'a> {
     /// Numbers of blank lines between last drawer's line and next non-blank
     /// line or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this drawer
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl Drawer<'_> {
@@ -34,6 +38,7 @@ impl Drawer<'_> {
             name: self.name.into_owned().into(),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -68,6 +73,7 @@ pub fn parse_drawer_without_blank(input: &str) -> IResult<&str, (Drawer, &str),
                 name: name.into(),
                 pre_blank: 0,
                 post_blank: 0,
+                affiliated: None,
             },
             contents,
         ),
@@ -88,7 +94,8 @@ fn parse() {
                 Drawer {
                     name: "PROPERTIES".into(),
                     pre_blank: 0,
-                    post_blank: 0
+                    post_blank: 0,
+                    affiliated: None,
                 },
                 "  :CUSTOM_ID: id\n"
             )
@@ -110,6 +117,7 @@ fn parse() {
                     name: "PROPERTIES".into(),
                     pre_blank: 2,
                     post_blank: 1,
+                    affiliated: None,
                 },
                 ""
             )
@@ -6,6 +6,7 @@ use nom::{
     IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, line, lines_till};
 
 /// Dynamic Block Element
@@ -24,6 +25,9 @@ pub struct DynBlock<'a> {
     /// Numbers of blank lines between last drawer's line and next non-blank
     /// line or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this block
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl DynBlock<'_> {
@@ -37,6 +41,7 @@ impl DynBlock<'_> {
             arguments: self.arguments.map(Into::into).map(Cow::Owned),
             pre_blank: self.pre_blank,
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -64,6 +69,7 @@ fn parse_internal(input: &str) -> IResult<&str, (DynBlock, &str), ()> {
                 },
                 pre_blank,
                 post_blank,
+                affiliated: None,
             },
             contents,
         ),
@@ -91,6 +97,7 @@ CONTENTS
                     arguments: Some(":scope file".into()),
                     pre_blank: 2,
                     post_blank: 1,
+                    affiliated: None,
                 },
                 "CONTENTS\n"
             )
@@ -0,0 +1,580 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    combinator::{opt, verify},
+    sequence::preceded,
+    IResult,
+};
+
+/// Entity Object
+///
+/// # Syntax
+///
+/// ```text
+/// \NAME
+/// \NAME{}
+/// ```
+///
+/// `NAME` must be listed in the built-in entities table, see [`EntityDef`].
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct Entity<'a> {
+    /// Entity name, without the leading backslash
+    pub name: Cow<'a, str>,
+    /// Whether this entity is followed by `{}`
+    pub brackets: bool,
+}
+
+impl Entity<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, Entity<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    /// Returns the definition of this entity in the built-in entities table.
+    pub fn definition(&self) -> Option<&'static EntityDef> {
+        EntityDef::get(&self.name)
+    }
+
+    pub fn into_owned(self) -> Entity<'static> {
+        Entity {
+            name: self.name.into_owned().into(),
+            brackets: self.brackets,
+        }
+    }
+}
+
+/// An entry of the built-in entities table, the same as `org-entities`
+#[derive(Debug)]
+pub struct EntityDef {
+    /// Entity name, e.g. `alpha`
+    pub name: &'static str,
+    /// LaTeX replacement, e.g. `\alpha`
+    pub latex: &'static str,
+    /// Whether the LaTeX replacement needs math mode
+    pub latex_math: bool,
+    /// HTML replacement, e.g. `&alpha;`
+    pub html: &'static str,
+    /// UTF-8 replacement, e.g. `α`
+    pub utf8: &'static str,
+}
+
+impl EntityDef {
+    /// Looks up an entity by its name.
+    ///
+    /// ```rust
+    /// use orgize::elements::EntityDef;
+    ///
+    /// let rarr = EntityDef::get("rarr").unwrap();
+    /// assert_eq!(rarr.html, "&rarr;");
+    /// assert_eq!(rarr.utf8, "→");
+    /// assert!(EntityDef::get("foo").is_none());
+    /// ```
+    pub fn get(name: &str) -> Option<&'static EntityDef> {
+        lazy_static::lazy_static! {
+            static ref ENTITY_MAP: HashMap<&'static str, EntityDef> = ENTITIES
+                .iter()
+                .map(|&(name, latex, latex_math, html, utf8)| {
+                    (
+                        name,
+                        EntityDef {
+                            name,
+                            latex,
+                            latex_math,
+                            html,
+                            utf8,
+                        },
+                    )
+                })
+                .collect();
+        }
+
+        ENTITY_MAP.get(name)
+    }
+}
+
+fn parse_internal(input: &str) -> IResult<&str, Entity<'_>, ()> {
+    let (input, name) = preceded(
+        tag("\\"),
+        verify(
+            alt((
+                tag("there4"),
+                tag("sup1"),
+                tag("sup2"),
+                tag("sup3"),
+                tag("frac12"),
+                tag("frac14"),
+                tag("frac34"),
+                take_while1(|c: char| c.is_ascii_alphabetic()),
+            )),
+            |name: &str| EntityDef::get(name).is_some(),
+        ),
+    )(input)?;
+    let (input, brackets) = opt(tag("{}"))(input)?;
+
+    Ok((
+        input,
+        Entity {
+            name: name.into(),
+            brackets: brackets.is_some(),
+        },
+    ))
+}
+
+// (name, latex, latex math mode, html, utf8)
+#[rustfmt::skip]
+static ENTITIES: &[(&str, &str, bool, &str, &str)] = &[
+    // Letters
+    // Latin
+    ("Agrave", "\\`{A}", false, "&Agrave;", "À"),
+    ("agrave", "\\`{a}", false, "&agrave;", "à"),
+    ("Aacute", "\\'{A}", false, "&Aacute;", "Á"),
+    ("aacute", "\\'{a}", false, "&aacute;", "á"),
+    ("Acirc", "\\^{A}", false, "&Acirc;", "Â"),
+    ("acirc", "\\^{a}", false, "&acirc;", "â"),
+    ("Amacr", "\\bar{A}", false, "&Amacr;", "Ā"),
+    ("amacr", "\\bar{a}", false, "&amacr;", "ā"),
+    ("Atilde", "\\~{A}", false, "&Atilde;", "Ã"),
+    ("atilde", "\\~{a}", false, "&atilde;", "ã"),
+    ("Auml", "\\\"{A}", false, "&Auml;", "Ä"),
+    ("auml", "\\\"{a}", false, "&auml;", "ä"),
+    ("Aring", "\\AA{}", false, "&Aring;", "Å"),
+    ("AA", "\\AA{}", false, "&Aring;", "Å"),
+    ("aring", "\\aa{}", false, "&aring;", "å"),
+    ("AElig", "\\AE{}", false, "&AElig;", "Æ"),
+    ("aelig", "\\ae{}", false, "&aelig;", "æ"),
+    ("Ccedil", "\\c{C}", false, "&Ccedil;", "Ç"),
+    ("ccedil", "\\c{c}", false, "&ccedil;", "ç"),
+    ("Egrave", "\\`{E}", false, "&Egrave;", "È"),
+    ("egrave", "\\`{e}", false, "&egrave;", "è"),
+    ("Eacute", "\\'{E}", false, "&Eacute;", "É"),
+    ("eacute", "\\'{e}", false, "&eacute;", "é"),
+    ("Ecirc", "\\^{E}", false, "&Ecirc;", "Ê"),
+    ("ecirc", "\\^{e}", false, "&ecirc;", "ê"),
+    ("Euml", "\\\"{E}", false, "&Euml;", "Ë"),
+    ("euml", "\\\"{e}", false, "&euml;", "ë"),
+    ("Igrave", "\\`{I}", false, "&Igrave;", "Ì"),
+    ("igrave", "\\`{i}", false, "&igrave;", "ì"),
+    ("Iacute", "\\'{I}", false, "&Iacute;", "Í"),
+    ("iacute", "\\'{i}", false, "&iacute;", "í"),
+    ("Idot", "\\.{I}", false, "&idot;", "İ"),
+    ("inodot", "\\i", false, "&inodot;", "ı"),
+    ("Icirc", "\\^{I}", false, "&Icirc;", "Î"),
+    ("icirc", "\\^{i}", false, "&icirc;", "î"),
+    ("Iuml", "\\\"{I}", false, "&Iuml;", "Ï"),
+    ("iuml", "\\\"{i}", false, "&iuml;", "ï"),
+    ("Ntilde", "\\~{N}", false, "&Ntilde;", "Ñ"),
+    ("ntilde", "\\~{n}", false, "&ntilde;", "ñ"),
+    ("Ograve", "\\`{O}", false, "&Ograve;", "Ò"),
+    ("ograve", "\\`{o}", false, "&ograve;", "ò"),
+    ("Oacute", "\\'{O}", false, "&Oacute;", "Ó"),
+    ("oacute", "\\'{o}", false, "&oacute;", "ó"),
+    ("Ocirc", "\\^{O}", false, "&Ocirc;", "Ô"),
+    ("ocirc", "\\^{o}", false, "&ocirc;", "ô"),
+    ("Otilde", "\\~{O}", false, "&Otilde;", "Õ"),
+    ("otilde", "\\~{o}", false, "&otilde;", "õ"),
+    ("Ouml", "\\\"{O}", false, "&Ouml;", "Ö"),
+    ("ouml", "\\\"{o}", false, "&ouml;", "ö"),
+    ("Oslash", "\\O", false, "&Oslash;", "Ø"),
+    ("oslash", "\\o{}", false, "&oslash;", "ø"),
+    ("OElig", "\\OE{}", false, "&OElig;", "Œ"),
+    ("oelig", "\\oe{}", false, "&oelig;", "œ"),
+    ("Scaron", "\\v{S}", false, "&Scaron;", "Š"),
+    ("scaron", "\\v{s}", false, "&scaron;", "š"),
+    ("szlig", "\\ss{}", false, "&szlig;", "ß"),
+    ("Ugrave", "\\`{U}", false, "&Ugrave;", "Ù"),
+    ("ugrave", "\\`{u}", false, "&ugrave;", "ù"),
+    ("Uacute", "\\'{U}", false, "&Uacute;", "Ú"),
+    ("uacute", "\\'{u}", false, "&uacute;", "ú"),
+    ("Ucirc", "\\^{U}", false, "&Ucirc;", "Û"),
+    ("ucirc", "\\^{u}", false, "&ucirc;", "û"),
+    ("Uuml", "\\\"{U}", false, "&Uuml;", "Ü"),
+    ("uuml", "\\\"{u}", false, "&uuml;", "ü"),
+    ("Yacute", "\\'{Y}", false, "&Yacute;", "Ý"),
+    ("yacute", "\\'{y}", false, "&yacute;", "ý"),
+    ("Yuml", "\\\"{Y}", false, "&Yuml;", "Ÿ"),
+    ("yuml", "\\\"{y}", false, "&yuml;", "ÿ"),
+    // Latin (special face)
+    ("fnof", "\\textit{f}", false, "&fnof;", "ƒ"),
+    ("real", "\\Re", true, "&real;", "ℜ"),
+    ("image", "\\Im", true, "&image;", "ℑ"),
+    ("weierp", "\\wp", true, "&weierp;", "℘"),
+    ("ell", "\\ell", true, "&ell;", "ℓ"),
+    ("imath", "\\imath", true, "&imath;", "ı"),
+    ("jmath", "\\jmath", true, "&jmath;", "ȷ"),
+    // Greek
+    ("Alpha", "A", false, "&Alpha;", "Α"),
+    ("alpha", "\\alpha", true, "&alpha;", "α"),
+    ("Beta", "B", false, "&Beta;", "Β"),
+    ("beta", "\\beta", true, "&beta;", "β"),
+    ("Gamma", "\\Gamma", true, "&Gamma;", "Γ"),
+    ("gamma", "\\gamma", true, "&gamma;", "γ"),
+    ("Delta", "\\Delta", true, "&Delta;", "Δ"),
+    ("delta", "\\delta", true, "&delta;", "δ"),
+    ("Epsilon", "E", false, "&Epsilon;", "Ε"),
+    ("epsilon", "\\epsilon", true, "&epsilon;", "ε"),
+    ("varepsilon", "\\varepsilon", true, "&epsilon;", "ε"),
+    ("Zeta", "Z", false, "&Zeta;", "Ζ"),
+    ("zeta", "\\zeta", true, "&zeta;", "ζ"),
+    ("Eta", "H", false, "&Eta;", "Η"),
+    ("eta", "\\eta", true, "&eta;", "η"),
+    ("Theta", "\\Theta", true, "&Theta;", "Θ"),
+    ("theta", "\\theta", true, "&theta;", "θ"),
+    ("thetasym", "\\vartheta", true, "&thetasym;", "ϑ"),
+    ("vartheta", "\\vartheta", true, "&thetasym;", "ϑ"),
+    ("Iota", "I", false, "&Iota;", "Ι"),
+    ("iota", "\\iota", true, "&iota;", "ι"),
+    ("Kappa", "K", false, "&Kappa;", "Κ"),
+    ("kappa", "\\kappa", true, "&kappa;", "κ"),
+    ("Lambda", "\\Lambda", true, "&Lambda;", "Λ"),
+    ("lambda", "\\lambda", true, "&lambda;", "λ"),
+    ("Mu", "M", false, "&Mu;", "Μ"),
+    ("mu", "\\mu", true, "&mu;", "μ"),
+    ("nu", "\\nu", true, "&nu;", "ν"),
+    ("Nu", "N", false, "&Nu;", "Ν"),
+    ("Xi", "\\Xi", true, "&Xi;", "Ξ"),
+    ("xi", "\\xi", true, "&xi;", "ξ"),
+    ("Omicron", "O", false, "&Omicron;", "Ο"),
+    ("omicron", "\\textit{o}", false, "&omicron;", "ο"),
+    ("Pi", "\\Pi", true, "&Pi;", "Π"),
+    ("pi", "\\pi", true, "&pi;", "π"),
+    ("Rho", "P", false, "&Rho;", "Ρ"),
+    ("rho", "\\rho", true, "&rho;", "ρ"),
+    ("Sigma", "\\Sigma", true, "&Sigma;", "Σ"),
+    ("sigma", "\\sigma", true, "&sigma;", "σ"),
+    ("sigmaf", "\\varsigma", true, "&sigmaf;", "ς"),
+    ("varsigma", "\\varsigma", true, "&sigmaf;", "ς"),
+    ("Tau", "T", false, "&Tau;", "Τ"),
+    ("Upsilon", "\\Upsilon", true, "&Upsilon;", "Υ"),
+    ("upsih", "\\Upsilon", true, "&upsih;", "ϒ"),
+    ("upsilon", "\\upsilon", true, "&upsilon;", "υ"),
+    ("Phi", "\\Phi", true, "&Phi;", "Φ"),
+    ("phi", "\\phi", true, "&phi;", "ɸ"),
+    ("varphi", "\\varphi", true, "&varphi;", "φ"),
+    ("Chi", "X", false, "&Chi;", "Χ"),
+    ("chi", "\\chi", true, "&chi;", "χ"),
+    ("acutex", "\\acute x", true, "&acute;x", "𝑥́"),
+    ("Psi", "\\Psi", true, "&Psi;", "Ψ"),
+    ("psi", "\\psi", true, "&psi;", "ψ"),
+    ("tau", "\\tau", true, "&tau;", "τ"),
+    ("Omega", "\\Omega", true, "&Omega;", "Ω"),
+    ("omega", "\\omega", true, "&omega;", "ω"),
+    ("piv", "\\varpi", true, "&piv;", "ϖ"),
+    ("varpi", "\\varpi", true, "&piv;", "ϖ"),
+    ("partial", "\\partial", true, "&part;", "∂"),
+    // Hebrew
+    ("alefsym", "\\aleph", true, "&alefsym;", "ℵ"),
+    ("aleph", "\\aleph", true, "&aleph;", "ℵ"),
+    ("gimel", "\\gimel", true, "&gimel;", "ℷ"),
+    ("beth", "\\beth", true, "&beth;", "ב"),
+    ("dalet", "\\daleth", true, "&daleth;", "ד"),
+    // Icelandic
+    ("ETH", "\\DH{}", false, "&ETH;", "Ð"),
+    ("eth", "\\dh{}", false, "&eth;", "ð"),
+    ("THORN", "\\TH{}", false, "&THORN;", "Þ"),
+    ("thorn", "\\th{}", false, "&thorn;", "þ"),
+    // Punctuation
+    // Dots and Marks
+    ("dots", "\\dots{}", false, "&hellip;", "…"),
+    ("cdots", "\\cdots{}", true, "&ctdot;", "⋯"),
+    ("hellip", "\\dots{}", false, "&hellip;", "…"),
+    ("middot", "\\textperiodcentered{}", false, "&middot;", "·"),
+    ("iexcl", "!`", false, "&iexcl;", "¡"),
+    ("iquest", "?`", false, "&iquest;", "¿"),
+    // Dash-like
+    ("shy", "\\-", false, "&shy;", ""),
+    ("ndash", "--", false, "&ndash;", "–"),
+    ("mdash", "---", false, "&mdash;", "—"),
+    // Quotations
+    ("quot", "\\textquotedbl{}", false, "&quot;", "\""),
+    ("acute", "\\textasciiacute{}", false, "&acute;", "´"),
+    ("ldquo", "\\textquotedblleft{}", false, "&ldquo;", "“"),
+    ("rdquo", "\\textquotedblright{}", false, "&rdquo;", "”"),
+    ("bdquo", "\\quotedblbase{}", false, "&bdquo;", "„"),
+    ("lsquo", "\\textquoteleft{}", false, "&lsquo;", "‘"),
+    ("rsquo", "\\textquoteright{}", false, "&rsquo;", "’"),
+    ("sbquo", "\\quotesinglbase{}", false, "&sbquo;", "‚"),
+    ("laquo", "\\guillemotleft{}", false, "&laquo;", "«"),
+    ("raquo", "\\guillemotright{}", false, "&raquo;", "»"),
+    ("lsaquo", "\\guilsinglleft{}", false, "&lsaquo;", "‹"),
+    ("rsaquo", "\\guilsinglright{}", false, "&rsaquo;", "›"),
+    // Other
+    // Misc. (often used)
+    ("circ", "\\^{}", false, "&circ;", "ˆ"),
+    ("vert", "\\vert{}", true, "&vert;", "|"),
+    ("vbar", "|", false, "|", "|"),
+    ("brvbar", "\\textbrokenbar{}", false, "&brvbar;", "¦"),
+    ("S", "\\S", false, "&sect;", "§"),
+    ("sect", "\\S", false, "&sect;", "§"),
+    ("amp", "\\&", false, "&amp;", "&"),
+    ("lt", "\\textless{}", false, "&lt;", "<"),
+    ("gt", "\\textgreater{}", false, "&gt;", ">"),
+    ("tilde", "\\textasciitilde{}", false, "~", "~"),
+    ("slash", "/", false, "/", "/"),
+    ("plus", "+", false, "+", "+"),
+    ("under", "\\_", false, "_", "_"),
+    ("equal", "=", false, "=", "="),
+    ("asciicirc", "\\textasciicircum{}", false, "^", "^"),
+    ("dagger", "\\textdagger{}", false, "&dagger;", "†"),
+    ("dag", "\\dag{}", false, "&dagger;", "†"),
+    ("Dagger", "\\textdaggerdbl{}", false, "&Dagger;", "‡"),
+    ("ddag", "\\ddag{}", false, "&Dagger;", "‡"),
+    // Whitespace
+    ("nbsp", "~", false, "&nbsp;", "\u{a0}"),
+    ("ensp", "\\hspace*{.5em}", false, "&ensp;", "\u{2002}"),
+    ("emsp", "\\hspace*{1em}", false, "&emsp;", "\u{2003}"),
+    ("thinsp", "\\hspace*{.2em}", false, "&thinsp;", "\u{2009}"),
+    // Currency
+    ("curren", "\\textcurrency{}", false, "&curren;", "¤"),
+    ("cent", "\\textcent{}", false, "&cent;", "¢"),
+    ("pound", "\\pounds{}", false, "&pound;", "£"),
+    ("yen", "\\textyen{}", false, "&yen;", "¥"),
+    ("euro", "\\texteuro{}", false, "&euro;", "€"),
+    ("EUR", "\\texteuro{}", false, "&euro;", "€"),
+    ("dollar", "\\$", false, "$", "$"),
+    ("USD", "\\$", false, "$", "$"),
+    // Property Marks
+    ("copy", "\\textcopyright{}", false, "&copy;", "©"),
+    ("reg", "\\textregistered{}", false, "&reg;", "®"),
+    ("trade", "\\texttrademark{}", false, "&trade;", "™"),
+    // Science et al.
+    ("minus", "\\minus", true, "&minus;", "−"),
+    ("pm", "\\textpm{}", false, "&plusmn;", "±"),
+    ("plusmn", "\\textpm{}", false, "&plusmn;", "±"),
+    ("times", "\\texttimes{}", false, "&times;", "×"),
+    ("frasl", "/", false, "&frasl;", "⁄"),
+    ("colon", "\\colon", true, ":", ":"),
+    ("div", "\\textdiv{}", false, "&divide;", "÷"),
+    ("frac12", "\\textonehalf{}", false, "&frac12;", "½"),
+    ("frac14", "\\textonequarter{}", false, "&frac14;", "¼"),
+    ("frac34", "\\textthreequarters{}", false, "&frac34;", "¾"),
+    ("permil", "\\textperthousand{}", false, "&permil;", "‰"),
+    ("sup1", "\\textonesuperior{}", false, "&sup1;", "¹"),
+    ("sup2", "\\texttwosuperior{}", false, "&sup2;", "²"),
+    ("sup3", "\\textthreesuperior{}", false, "&sup3;", "³"),
+    ("radic", "\\sqrt{\\,}", true, "&radic;", "√"),
+    ("sum", "\\sum", true, "&sum;", "∑"),
+    ("prod", "\\prod", true, "&prod;", "∏"),
+    ("micro", "\\textmu{}", false, "&micro;", "µ"),
+    ("macr", "\\textasciimacron{}", false, "&macr;", "¯"),
+    ("deg", "\\textdegree{}", false, "&deg;", "°"),
+    ("prime", "\\prime", true, "&prime;", "′"),
+    ("Prime", "\\prime{}\\prime", true, "&Prime;", "″"),
+    ("infin", "\\infty", true, "&infin;", "∞"),
+    ("infty", "\\infty", true, "&infin;", "∞"),
+    ("prop", "\\propto", true, "&prop;", "∝"),
+    ("propto", "\\propto", true, "&prop;", "∝"),
+    ("not", "\\textlnot{}", false, "&not;", "¬"),
+    ("neg", "\\neg{}", true, "&not;", "¬"),
+    ("land", "\\land", true, "&and;", "∧"),
+    ("wedge", "\\wedge", true, "&and;", "∧"),
+    ("lor", "\\lor", true, "&or;", "∨"),
+    ("vee", "\\vee", true, "&or;", "∨"),
+    ("cap", "\\cap", true, "&cap;", "∩"),
+    ("cup", "\\cup", true, "&cup;", "∪"),
+    ("smile", "\\smile", true, "&smile;", "⌣"),
+    ("frown", "\\frown", true, "&frown;", "⌢"),
+    ("int", "\\int", true, "&int;", "∫"),
+    ("therefore", "\\therefore", true, "&there4;", "∴"),
+    ("there4", "\\therefore", true, "&there4;", "∴"),
+    ("because", "\\because", true, "&because;", "∵"),
+    ("sim", "\\sim", true, "&sim;", "∼"),
+    ("cong", "\\cong", true, "&cong;", "≅"),
+    ("simeq", "\\simeq", true, "&cong;", "≅"),
+    ("asymp", "\\asymp", true, "&asymp;", "≍"),
+    ("approx", "\\approx", true, "&asymp;", "≈"),
+    ("ne", "\\ne", true, "&ne;", "≠"),
+    ("neq", "\\neq", true, "&ne;", "≠"),
+    ("equiv", "\\equiv", true, "&equiv;", "≡"),
+    ("triangleq", "\\triangleq", true, "&triangleq;", "≜"),
+    ("le", "\\le", true, "&le;", "≤"),
+    ("leq", "\\le", true, "&le;", "≤"),
+    ("ge", "\\ge", true, "&ge;", "≥"),
+    ("geq", "\\ge", true, "&ge;", "≥"),
+    ("lessgtr", "\\lessgtr", true, "&lessgtr;", "≶"),
+    ("lesseqgtr", "\\lesseqgtr", true, "&lesseqgtr;", "⋚"),
+    ("ll", "\\ll", true, "&Lt;", "≪"),
+    ("Ll", "\\lll", true, "&Ll;", "⋘"),
+    ("lll", "\\lll", true, "&Ll;", "⋘"),
+    ("gg", "\\gg", true, "&Gt;", "≫"),
+    ("Gg", "\\ggg", true, "&Gg;", "⋙"),
+    ("ggg", "\\ggg", true, "&Gg;", "⋙"),
+    ("prec", "\\prec", true, "&pr;", "≺"),
+    ("preceq", "\\preceq", true, "&prcue;", "≼"),
+    ("preccurlyeq", "\\preccurlyeq", true, "&prcue;", "≼"),
+    ("succ", "\\succ", true, "&sc;", "≻"),
+    ("succeq", "\\succeq", true, "&sccue;", "≽"),
+    ("succcurlyeq", "\\succcurlyeq", true, "&sccue;", "≽"),
+    ("sub", "\\subset", true, "&sub;", "⊂"),
+    ("subset", "\\subset", true, "&sub;", "⊂"),
+    ("sup", "\\supset", true, "&sup;", "⊃"),
+    ("supset", "\\supset", true, "&sup;", "⊃"),
+    ("nsub", "\\not\\subset", true, "&nsub;", "⊄"),
+    ("sube", "\\subseteq", true, "&sube;", "⊆"),
+    ("nsup", "\\not\\supset", true, "&nsup;", "⊅"),
+    ("supe", "\\supseteq", true, "&supe;", "⊇"),
+    ("setminus", "\\setminus", true, "&setminus;", "⧵"),
+    ("forall", "\\forall", true, "&forall;", "∀"),
+    ("exist", "\\exists", true, "&exist;", "∃"),
+    ("exists", "\\exists", true, "&exist;", "∃"),
+    ("nexist", "\\nexists", true, "&exist;", "∄"),
+    ("nexists", "\\nexists", true, "&exist;", "∄"),
+    ("empty", "\\emptyset", true, "&empty;", "∅"),
+    ("emptyset", "\\emptyset", true, "&empty;", "∅"),
+    ("isin", "\\in", true, "&isin;", "∈"),
+    ("in", "\\in", true, "&isin;", "∈"),
+    ("notin", "\\notin", true, "&notin;", "∉"),
+    ("ni", "\\ni", true, "&ni;", "∋"),
+    ("nabla", "\\nabla", true, "&nabla;", "∇"),
+    ("ang", "\\angle", true, "&ang;", "∠"),
+    ("angle", "\\angle", true, "&ang;", "∠"),
+    ("perp", "\\perp", true, "&perp;", "⊥"),
+    ("parallel", "\\parallel", true, "&parallel;", "∥"),
+    ("sdot", "\\cdot", true, "&sdot;", "⋅"),
+    ("cdot", "\\cdot", true, "&sdot;", "⋅"),
+    ("lceil", "\\lceil", true, "&lceil;", "⌈"),
+    ("rceil", "\\rceil", true, "&rceil;", "⌉"),
+    ("lfloor", "\\lfloor", true, "&lfloor;", "⌊"),
+    ("rfloor", "\\rfloor", true, "&rfloor;", "⌋"),
+    ("lang", "\\langle", true, "&lang;", "⟨"),
+    ("rang", "\\rangle", true, "&rang;", "⟩"),
+    ("langle", "\\langle", true, "&lang;", "⟨"),
+    ("rangle", "\\rangle", true, "&rang;", "⟩"),
+    ("hbar", "\\hbar", true, "&hbar;", "ℏ"),
+    ("mho", "\\mho", true, "&mho;", "℧"),
+    // Arrows
+    ("larr", "\\leftarrow", true, "&larr;", "←"),
+    ("leftarrow", "\\leftarrow", true, "&larr;", "←"),
+    ("gets", "\\gets", true, "&larr;", "←"),
+    ("lArr", "\\Leftarrow", true, "&lArr;", "⇐"),
+    ("Leftarrow", "\\Leftarrow", true, "&lArr;", "⇐"),
+    ("uarr", "\\uparrow", true, "&uarr;", "↑"),
+    ("uparrow", "\\uparrow", true, "&uarr;", "↑"),
+    ("uArr", "\\Uparrow", true, "&uArr;", "⇑"),
+    ("Uparrow", "\\Uparrow", true, "&uArr;", "⇑"),
+    ("rarr", "\\rightarrow", true, "&rarr;", "→"),
+    ("to", "\\to", true, "&rarr;", "→"),
+    ("rightarrow", "\\rightarrow", true, "&rarr;", "→"),
+    ("rArr", "\\Rightarrow", true, "&rArr;", "⇒"),
+    ("Rightarrow", "\\Rightarrow", true, "&rArr;", "⇒"),
+    ("darr", "\\downarrow", true, "&darr;", "↓"),
+    ("downarrow", "\\downarrow", true, "&darr;", "↓"),
+    ("dArr", "\\Downarrow", true, "&dArr;", "⇓"),
+    ("Downarrow", "\\Downarrow", true, "&dArr;", "⇓"),
+    ("harr", "\\leftrightarrow", true, "&harr;", "↔"),
+    ("leftrightarrow", "\\leftrightarrow", true, "&harr;", "↔"),
+    ("hArr", "\\Leftrightarrow", true, "&hArr;", "⇔"),
+    ("Leftrightarrow", "\\Leftrightarrow", true, "&hArr;", "⇔"),
+    ("crarr", "\\hookleftarrow", true, "&crarr;", "↵"),
+    ("hookleftarrow", "\\hookleftarrow", true, "&crarr;", "↵"),
+    // Function names
+    ("arccos", "\\arccos", true, "arccos", "arccos"),
+    ("arcsin", "\\arcsin", true, "arcsin", "arcsin"),
+    ("arctan", "\\arctan", true, "arctan", "arctan"),
+    ("arg", "\\arg", true, "arg", "arg"),
+    ("cos", "\\cos", true, "cos", "cos"),
+    ("cosh", "\\cosh", true, "cosh", "cosh"),
+    ("cot", "\\cot", true, "cot", "cot"),
+    ("coth", "\\coth", true, "coth", "coth"),
+    ("csc", "\\csc", true, "csc", "csc"),
+    ("det", "\\det", true, "det", "det"),
+    ("dim", "\\dim", true, "dim", "dim"),
+    ("exp", "\\exp", true, "exp", "exp"),
+    ("gcd", "\\gcd", true, "gcd", "gcd"),
+    ("hom", "\\hom", true, "hom", "hom"),
+    ("inf", "\\inf", true, "inf", "inf"),
+    ("ker", "\\ker", true, "ker", "ker"),
+    ("lg", "\\lg", true, "lg", "lg"),
+    ("lim", "\\lim", true, "lim", "lim"),
+    ("liminf", "\\liminf", true, "liminf", "liminf"),
+    ("limsup", "\\limsup", true, "limsup", "limsup"),
+    ("ln", "\\ln", true, "ln", "ln"),
+    ("log", "\\log", true, "log", "log"),
+    ("max", "\\max", true, "max", "max"),
+    ("min", "\\min", true, "min", "min"),
+    ("Pr", "\\Pr", true, "Pr", "Pr"),
+    ("sec", "\\sec", true, "sec", "sec"),
+    ("sin", "\\sin", true, "sin", "sin"),
+    ("sinh", "\\sinh", true, "sinh", "sinh"),
+    ("tan", "\\tan", true, "tan", "tan"),
+    ("tanh", "\\tanh", true, "tanh", "tanh"),
+    // Signs & Symbols
+    ("bull", "\\textbullet{}", false, "&bull;", "•"),
+    ("bullet", "\\textbullet{}", false, "&bull;", "•"),
+    ("star", "\\star", true, "*", "⋆"),
+    ("lowast", "\\ast", true, "&lowast;", "∗"),
+    ("ast", "\\ast", true, "&lowast;", "*"),
+    ("odot", "\\odot", true, "o", "ʘ"),
+    ("oplus", "\\oplus", true, "&oplus;", "⊕"),
+    ("otimes", "\\otimes", true, "&otimes;", "⊗"),
+    ("check", "\\checkmark", true, "&checkmark;", "✓"),
+    ("checkmark", "\\checkmark", true, "&check;", "✓"),
+    // Miscellaneous (seldom used)
+    ("para", "\\P{}", false, "&para;", "¶"),
+    ("ordf", "\\textordfeminine{}", false, "&ordf;", "ª"),
+    ("ordm", "\\textordmasculine{}", false, "&ordm;", "º"),
+    ("cedil", "\\c{}", false, "&cedil;", "¸"),
+    ("oline", "\\overline{~}", true, "&oline;", "‾"),
+    ("uml", "\\textasciidieresis{}", false, "&uml;", "¨"),
+    ("zwnj", "\\/{}", false, "&zwnj;", "\u{200c}"),
+    ("zwj", "", false, "&zwj;", "\u{200d}"),
+    ("lrm", "", false, "&lrm;", "\u{200e}"),
+    ("rlm", "", false, "&rlm;", "\u{200f}"),
+    // Smilies
+    ("smiley", "\\ddot\\smile", true, "&#9786;", "☺"),
+    ("blacksmile", "\\ddot\\smile", true, "&#9787;", "☻"),
+    ("sad", "\\ddot\\frown", true, "&#9785;", "☹"),
+    ("frowny", "\\ddot\\frown", true, "&#9785;", "☹"),
+    // Suits
+    ("clubs", "\\clubsuit", true, "&clubs;", "♣"),
+    ("clubsuit", "\\clubsuit", true, "&clubs;", "♣"),
+    ("spades", "\\spadesuit", true, "&spades;", "♠"),
+    ("spadesuit", "\\spadesuit", true, "&spades;", "♠"),
+    ("hearts", "\\heartsuit", true, "&hearts;", "♥"),
+    ("heartsuit", "\\heartsuit", true, "&heartsuit;", "♥"),
+    ("diams", "\\diamondsuit", true, "&diams;", "◆"),
+    ("diamondsuit", "\\diamondsuit", true, "&diams;", "◆"),
+    ("diamond", "\\diamondsuit", true, "&diamond;", "◆"),
+    ("Diamond", "\\diamondsuit", true, "&diamond;", "◆"),
+    ("loz", "\\lozenge", true, "&loz;", "◊"),
+];
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Entity::parse("\\alpha text"),
+        Some((
+            " text",
+            Entity {
+                name: "alpha".into(),
+                brackets: false
+            }
+        ))
+    );
+    assert_eq!(
+        Entity::parse("\\frac12{}x"),
+        Some((
+            "x",
+            Entity {
+                name: "frac12".into(),
+                brackets: true
+            }
+        ))
+    );
+    assert_eq!(
+        Entity::parse("\\there4."),
+        Some((
+            ".",
+            Entity {
+                name: "there4".into(),
+                brackets: false
+            }
+        ))
+    );
+    assert!(Entity::parse("\\alphax").is_none());
+    assert!(Entity::parse("\\foo").is_none());
+    assert!(Entity::parse("alpha").is_none());
+}
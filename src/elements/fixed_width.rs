@@ -5,6 +5,7 @@ use nom::{
     Err, IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, lines_while};
 
 #[derive(Debug, Default, Clone)]
@@ -16,6 +17,9 @@ pub struct FixedWidth<'a> {
     /// Numbers of blank lines between last fixed width's line and next
     /// non-blank line or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this fixed width area
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl FixedWidth<'_> {
@@ -27,6 +31,7 @@ impl FixedWidth<'_> {
         FixedWidth {
             value: self.value.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -49,6 +54,7 @@ fn parse_internal(input: &str) -> IResult<&str, FixedWidth, ()> {
         FixedWidth {
             value: value.into(),
             post_blank,
+            affiliated: None,
         },
     ))
 }
@@ -73,7 +79,8 @@ fn parse() {
 : C
 "#
                 .into(),
-                post_blank: 1
+                post_blank: 1,
+                affiliated: None,
             }
         ))
     );
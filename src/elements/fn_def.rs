@@ -6,6 +6,7 @@ use nom::{
     IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, line};
 
 /// Footnote Definition Element
@@ -18,6 +19,9 @@ pub struct FnDef<'a> {
     /// Numbers of blank lines between last footnote definition's line and next
     /// non-blank line or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this footnote definition
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl FnDef<'_> {
@@ -29,6 +33,7 @@ impl FnDef<'_> {
         FnDef {
             label: self.label.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
         }
     }
 }
@@ -50,6 +55,7 @@ fn parse_internal(input: &str) -> IResult<&str, (FnDef, &str), ()> {
             FnDef {
                 label: label.into(),
                 post_blank,
+                affiliated: None,
             },
             content,
         ),
@@ -65,7 +71,8 @@ fn parse() {
             (
                 FnDef {
                     label: "1".into(),
-                    post_blank: 0
+                    post_blank: 0,
+                    affiliated: None,
                 },
                 " https://orgmode.org"
             )
@@ -79,6 +86,7 @@ fn parse() {
                 FnDef {
                     label: "word_1".into(),
                     post_blank: 0,
+                    affiliated: None,
                 },
                 " https://orgmode.org"
             )
@@ -92,6 +100,7 @@ fn parse() {
                 FnDef {
                     label: "WORD-1".into(),
                     post_blank: 0,
+                    affiliated: None,
                 },
                 " https://orgmode.org"
             )
@@ -105,6 +114,7 @@ fn parse() {
                 FnDef {
                     label: "WORD".into(),
                     post_blank: 0,
+                    affiliated: None,
                 },
                 ""
             )
@@ -0,0 +1,365 @@
+use std::borrow::Cow;
+
+/// Babel Header Arguments
+///
+/// # Syntax
+///
+/// ```text
+/// :NAME VALUE :NAME VALUE ...
+/// ```
+///
+/// Arguments are stored in order of appearance and the same name may
+/// appear more than once. Except for `:var`, whose assignments accumulate,
+/// the last value of an argument takes precedence, so inherited defaults
+/// are merged by inserting them first.
+///
+/// ```rust
+/// use orgize::elements::{Exports, HeaderArgs, Tangle};
+///
+/// let args = HeaderArgs::parse(":var x=1 :results output silent :exports both :tangle yes");
+///
+/// assert_eq!(args.vars(), vec![("x", "1")]);
+/// assert_eq!(args.results().collection, Some("output"));
+/// assert_eq!(args.results().handling, Some("silent"));
+/// assert_eq!(args.exports(), Some(Exports::Both));
+/// assert_eq!(args.tangle(), Some(Tangle::Yes));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct HeaderArgs<'a> {
+    /// Argument names without the leading colon, and their values
+    pub pairs: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+/// Value of `:results` header argument, grouped by category
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct HeaderResults<'a> {
+    /// `value` or `output`
+    pub collection: Option<&'a str>,
+    /// `table`, `vector`, `list`, `scalar`, `verbatim` or `file`
+    pub ty: Option<&'a str>,
+    /// `code`, `drawer`, `html`, `latex`, `link`, `graphics`, `org`, `pp`
+    /// or `raw`
+    pub format: Option<&'a str>,
+    /// `replace`, `silent`, `none`, `discard`, `append` or `prepend`
+    pub handling: Option<&'a str>,
+}
+
+/// Value of `:exports` header argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exports {
+    Code,
+    Results,
+    Both,
+    None,
+}
+
+/// Value of `:tangle` header argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tangle<'a> {
+    Yes,
+    No,
+    /// Tangles to the given file
+    File(&'a str),
+}
+
+/// Value of `:noweb` header argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Noweb {
+    Yes,
+    No,
+    Tangle,
+    NoExport,
+    StripExport,
+    StripTangle,
+    Eval,
+}
+
+impl<'a> HeaderArgs<'a> {
+    /// Parses header arguments from a string.
+    ///
+    /// Text before the first argument name is ignored. Colons inside
+    /// quotes, parentheses or brackets don't start a new argument.
+    pub fn parse(input: &'a str) -> HeaderArgs<'a> {
+        HeaderArgs {
+            pairs: split(input)
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Appends an argument.
+    pub fn push(&mut self, name: impl Into<Cow<'a, str>>, value: impl Into<Cow<'a, str>>) {
+        self.pairs.push((name.into(), value.into()));
+    }
+
+    /// Appends arguments from `other`, which take precedence over
+    /// existing ones.
+    pub fn extend(&mut self, other: HeaderArgs<'a>) {
+        self.pairs.extend(other.pairs);
+    }
+
+    /// Returns the last value of argument `name`, ignoring case.
+    /// Arguments without value are returned as an empty string.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .rev()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| &**value)
+    }
+
+    /// Returns all values of argument `name` in order, ignoring case.
+    pub fn get_all<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'b str> + 'b {
+        self.pairs
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| &**value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Cow<'a, str>, Cow<'a, str>)> {
+        self.pairs.iter()
+    }
+
+    /// Returns variable assignments from all `:var` arguments.
+    ///
+    /// Assignments are separated by commas or whitespaces, a variable
+    /// assigned more than once keeps its first position and last value.
+    ///
+    /// ```rust
+    /// use orgize::elements::HeaderArgs;
+    ///
+    /// let args = HeaderArgs::parse(r#":var a=1, b="x y" :var c = (list 1 2) a=tbl[0,1]"#);
+    ///
+    /// assert_eq!(
+    ///     args.vars(),
+    ///     vec![("a", "tbl[0,1]"), ("b", "\"x y\""), ("c", "(list 1 2)")]
+    /// );
+    /// ```
+    pub fn vars(&self) -> Vec<(&str, &str)> {
+        let mut vars: Vec<(&str, &str)> = Vec::new();
+        for (name, value) in self.get_all("var").flat_map(assignments) {
+            match vars.iter_mut().find(|(n, _)| *n == name) {
+                Some(var) => var.1 = value,
+                None => vars.push((name, value)),
+            }
+        }
+        vars
+    }
+
+    /// Returns the value of `:results` arguments.
+    ///
+    /// Each category is set by the last keyword of that category, so
+    /// inherited keywords are only overridden by the same category.
+    pub fn results(&self) -> HeaderResults<'_> {
+        let mut results = HeaderResults::default();
+        for word in self.get_all("results").flat_map(str::split_whitespace) {
+            let category = match word {
+                "value" | "output" => &mut results.collection,
+                "table" | "vector" | "list" | "scalar" | "verbatim" | "file" => &mut results.ty,
+                "code" | "drawer" | "html" | "latex" | "link" | "graphics" | "org" | "pp"
+                | "raw" => &mut results.format,
+                "replace" | "silent" | "none" | "discard" | "append" | "prepend" => {
+                    &mut results.handling
+                }
+                _ => continue,
+            };
+            *category = Some(word);
+        }
+        results
+    }
+
+    /// Returns the value of `:exports` argument, or `None` if it's absent
+    /// or invalid.
+    pub fn exports(&self) -> Option<Exports> {
+        match self.get("exports")? {
+            "code" => Some(Exports::Code),
+            "results" => Some(Exports::Results),
+            "both" => Some(Exports::Both),
+            "none" => Some(Exports::None),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of `:tangle` argument, or `None` if it's absent.
+    ///
+    /// An empty value means `yes`.
+    pub fn tangle(&self) -> Option<Tangle<'_>> {
+        match self.get("tangle")? {
+            "" | "yes" => Some(Tangle::Yes),
+            "no" => Some(Tangle::No),
+            file => Some(Tangle::File(unquote(file))),
+        }
+    }
+
+    /// Returns the value of `:noweb` argument, or `None` if it's absent or
+    /// invalid.
+    pub fn noweb(&self) -> Option<Noweb> {
+        match self.get("noweb")? {
+            "yes" => Some(Noweb::Yes),
+            "no" => Some(Noweb::No),
+            "tangle" => Some(Noweb::Tangle),
+            "no-export" => Some(Noweb::NoExport),
+            "strip-export" => Some(Noweb::StripExport),
+            "strip-tangle" => Some(Noweb::StripTangle),
+            "eval" => Some(Noweb::Eval),
+            _ => None,
+        }
+    }
+
+    /// Returns the session name from `:session` argument, or `None` if
+    /// it's absent or `none`.
+    ///
+    /// An empty string means the default session.
+    pub fn session(&self) -> Option<&str> {
+        self.get("session")
+            .filter(|session| *session != "none")
+            .map(unquote)
+    }
+
+    pub fn into_owned(self) -> HeaderArgs<'static> {
+        HeaderArgs {
+            pairs: self
+                .pairs
+                .into_iter()
+                .map(|(k, v)| (k.into_owned().into(), v.into_owned().into()))
+                .collect(),
+        }
+    }
+}
+
+// splits `:name value :name value` into pairs
+fn split(input: &str) -> Vec<(&str, &str)> {
+    let mut starts = Vec::new();
+    let mut end = 0;
+    while end < input.len() {
+        let len = token_len(&input[end..], |c| c.is_whitespace());
+        if len == 0 {
+            end += input[end..].chars().next().map_or(1, char::len_utf8);
+        } else {
+            if input[end..].starts_with(':') {
+                starts.push(end);
+            }
+            end += len;
+        }
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let arg = &input[start + 1..starts.get(i + 1).copied().unwrap_or(input.len())];
+            match arg.find(char::is_whitespace) {
+                Some(i) => (&arg[0..i], arg[i..].trim()),
+                None => (arg, ""),
+            }
+        })
+        .collect()
+}
+
+// splits `a=1, b=2 c` into assignments
+fn assignments(input: &str) -> Vec<(&str, &str)> {
+    let mut assignments = Vec::new();
+    let mut input = input;
+
+    loop {
+        input = input.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if input.is_empty() {
+            break;
+        }
+        let len = input
+            .find(|c: char| c.is_whitespace() || c == ',' || c == '=')
+            .unwrap_or(input.len());
+        let name = &input[0..len];
+        input = input[len..].trim_start();
+        let value = match input.strip_prefix('=') {
+            Some(tail) => {
+                let tail = tail.trim_start();
+                let len = token_len(tail, |c| c.is_whitespace() || c == ',');
+                input = &tail[len..];
+                &tail[0..len]
+            }
+            None => "",
+        };
+        if !name.is_empty() {
+            assignments.push((name, value));
+        }
+    }
+
+    assignments
+}
+
+// length of a token ending at a separator outside quotes and brackets
+fn token_len(input: &str, is_separator: impl Fn(char) -> bool) -> usize {
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ if quoted => (),
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            c if depth == 0 && is_separator(c) => return i,
+            _ => (),
+        }
+    }
+
+    input.len()
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        HeaderArgs::parse("ignored :var x=1 :results  output silent :cache"),
+        HeaderArgs {
+            pairs: vec![
+                ("var".into(), "x=1".into()),
+                ("results".into(), "output silent".into()),
+                ("cache".into(), "".into()),
+            ]
+        }
+    );
+    assert_eq!(
+        HeaderArgs::parse(r#":var s="a :b" l=(f :c) :file "x y.png""#).pairs,
+        vec![
+            ("var".into(), r#"s="a :b" l=(f :c)"#.into()),
+            ("file".into(), r#""x y.png""#.into()),
+        ]
+    );
+
+    let mut args = HeaderArgs::parse(":results table silent :exports both :session");
+    args.extend(HeaderArgs::parse(
+        ":results output :exports nil :tangle \"src/main.rs\" :noweb no-export",
+    ));
+    assert_eq!(
+        args.results(),
+        HeaderResults {
+            collection: Some("output"),
+            ty: Some("table"),
+            format: None,
+            handling: Some("silent"),
+        }
+    );
+    assert_eq!(args.exports(), None);
+    assert_eq!(args.tangle(), Some(Tangle::File("src/main.rs")));
+    assert_eq!(args.noweb(), Some(Noweb::NoExport));
+    assert_eq!(args.session(), Some(""));
+    assert_eq!(HeaderArgs::parse(":session none").session(), None);
+    assert_eq!(HeaderArgs::parse(":var x").vars(), vec![("x", "")]);
+}
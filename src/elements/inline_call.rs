@@ -7,6 +7,8 @@ use nom::{
     IResult,
 };
 
+use crate::elements::HeaderArgs;
+
 /// Inline Babel Call Object
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
@@ -37,6 +39,31 @@ impl InlineCall<'_> {
             end_header: self.end_header.map(Into::into).map(Cow::Owned),
         }
     }
+
+    /// Returns header arguments of this call: arguments as a `:var`,
+    /// followed by inside and end header arguments.
+    pub fn header_args(&self) -> HeaderArgs<'_> {
+        call_header_args(
+            &self.arguments,
+            self.inside_header.as_deref(),
+            self.end_header.as_deref(),
+        )
+    }
+}
+
+pub(crate) fn call_header_args<'a>(
+    arguments: &'a str,
+    inside_header: Option<&'a str>,
+    end_header: Option<&'a str>,
+) -> HeaderArgs<'a> {
+    let mut args = HeaderArgs::default();
+    if !arguments.trim().is_empty() {
+        args.push("var", arguments.trim());
+    }
+    for header in inside_header.into_iter().chain(end_header) {
+        args.extend(HeaderArgs::parse(header));
+    }
+    args
 }
 
 #[inline]
@@ -7,6 +7,8 @@ use nom::{
     IResult,
 };
 
+use crate::elements::HeaderArgs;
+
 /// Inline Src Block Object
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
@@ -33,6 +35,14 @@ impl InlineSrc<'_> {
             body: self.body.into_owned().into(),
         }
     }
+
+    /// Parses header arguments from options.
+    pub fn header_args(&self) -> HeaderArgs<'_> {
+        self.options
+            .as_deref()
+            .map(HeaderArgs::parse)
+            .unwrap_or_default()
+    }
 }
 
 #[inline]
@@ -0,0 +1,120 @@
+use nom::{bytes::complete::take_while1, character::complete::space1, combinator::verify, IResult};
+
+use crate::parse::combinators::{blank_lines_count, line, lines_till};
+
+/// Inlinetask Element
+///
+/// # Syntax
+///
+/// ```text
+/// *************** TODO [#A] Title :tag:
+/// CONTENTS
+/// *************** END
+/// ```
+///
+/// The first child of an inlinetask is always its `Title`, followed by its
+/// contents. An inlinetask without the `END` line consists of a single line.
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct Inlinetask {
+    /// Inlinetask level, number of stars
+    pub level: usize,
+    /// Whether this inlinetask is closed by an `END` line
+    pub has_end: bool,
+    /// Numbers of blank lines between last inlinetask's line and next
+    /// non-blank line or buffer's end
+    pub post_blank: usize,
+}
+
+impl Inlinetask {
+    /// Parses an inlinetask, returns its title and contents if succeed.
+    pub(crate) fn parse(input: &str, min_level: usize) -> Option<(&str, (Inlinetask, &str))> {
+        parse_internal(input, min_level).ok()
+    }
+}
+
+fn parse_internal(input: &str, min_level: usize) -> IResult<&str, (Inlinetask, &str), ()> {
+    let (_, first_line) = verify(line, |line: &str| !is_end_line(line, min_level))(input)?;
+    let (first_line, stars) = verify(take_while1(|c: char| c == '*'), |s: &str| {
+        s.len() >= min_level
+    })(first_line)?;
+    let _ = verify(line, |s: &str| {
+        s.is_empty() || s.starts_with(&[' ', '\t'][..])
+    })(first_line)?;
+    let level = stars.len();
+
+    if let Ok((tail, content)) = lines_till(|line| is_end_line(line, min_level))(input) {
+        let (tail, post_blank) = blank_lines_count(tail)?;
+        Ok((
+            tail,
+            (
+                Inlinetask {
+                    level,
+                    has_end: true,
+                    post_blank,
+                },
+                content,
+            ),
+        ))
+    } else {
+        let (tail, _) = line(input)?;
+        let content = &input[0..input.len() - tail.len()];
+        let (tail, post_blank) = blank_lines_count(tail)?;
+        Ok((
+            tail,
+            (
+                Inlinetask {
+                    level,
+                    has_end: false,
+                    post_blank,
+                },
+                content,
+            ),
+        ))
+    }
+}
+
+fn is_end_line(line: &str, min_level: usize) -> bool {
+    take_while1::<_, _, ()>(|c: char| c == '*')(line)
+        .and_then(|(tail, stars)| {
+            let (tail, _) = space1(tail)?;
+            Ok(stars.len() >= min_level && tail.trim_end() == "END")
+        })
+        .unwrap_or(false)
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Inlinetask::parse("*** TODO task\nbody\n*** END\n\n", 3),
+        Some((
+            "",
+            (
+                Inlinetask {
+                    level: 3,
+                    has_end: true,
+                    post_blank: 1,
+                },
+                "*** TODO task\nbody\n"
+            )
+        ))
+    );
+    assert_eq!(
+        Inlinetask::parse("**** task\ntext", 3),
+        Some((
+            "text",
+            (
+                Inlinetask {
+                    level: 4,
+                    has_end: false,
+                    post_blank: 0,
+                },
+                "**** task\n"
+            )
+        ))
+    );
+    assert!(Inlinetask::parse("** task\n** END", 3).is_none());
+    assert!(Inlinetask::parse("*** END", 3).is_none());
+    assert!(Inlinetask::parse("***task", 3).is_none());
+}
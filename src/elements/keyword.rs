@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-use crate::elements::Element;
+use crate::elements::{inline_call::call_header_args, Element, HeaderArgs};
 use crate::parse::combinators::{blank_lines_count, line};
 
 /// Keyword Element
@@ -48,6 +48,9 @@ pub struct BabelCall<'a> {
     /// Numbers of blank lines between babel call line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this babel call
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 impl BabelCall<'_> {
@@ -55,10 +58,307 @@ impl BabelCall<'_> {
         BabelCall {
             value: self.value.into_owned().into(),
             post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
+
+    /// Returns the name of the called code block.
+    pub fn name(&self) -> &str {
+        split_call(&self.value).0
+    }
+
+    /// Returns this call without its end header arguments, e.g. `f(x=1)`
+    /// for `f(x=1) :exports none`, as referred to by `#+RESULTS:`.
+    pub fn call(&self) -> &str {
+        let value = self.value.trim();
+        match split_call(value).3 {
+            Some(end_header) => value[0..value.len() - end_header.len()].trim_end(),
+            None => value,
+        }
+    }
+
+    /// Returns header arguments of this call: arguments as a `:var`,
+    /// followed by inside and end header arguments, then the ones from
+    /// `#+HEADER:` keywords.
+    ///
+    /// ```rust
+    /// use orgize::{elements::BabelCall, Element, Org};
+    ///
+    /// let org = Org::parse("#+CALL: double[:results raw](n=4) :results silent");
+    /// let call = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         orgize::Event::Start(Element::BabelCall(call)) => Some(call),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(call.name(), "double");
+    /// assert_eq!(call.call(), "double[:results raw](n=4)");
+    /// assert_eq!(call.header_args().vars(), vec![("n", "4")]);
+    /// assert_eq!(call.header_args().results().handling, Some("silent"));
+    /// assert_eq!(call.header_args().results().format, Some("raw"));
+    /// ```
+    pub fn header_args(&self) -> HeaderArgs<'_> {
+        let (_, inside_header, arguments, end_header) = split_call(&self.value);
+        let mut args = call_header_args(arguments, inside_header, end_header);
+        for header in self.affiliated.iter().flat_map(|a| &a.header) {
+            args.extend(HeaderArgs::parse(header));
+        }
+        args
+    }
+}
+
+// splits `NAME[INSIDE-HEADER](ARGUMENTS) END-HEADER`
+fn split_call(value: &str) -> (&str, Option<&str>, &str, Option<&str>) {
+    let value = value.trim();
+    let name_end = value.find(&['[', '('][..]).unwrap_or(value.len());
+    let (name, mut tail) = value.split_at(name_end);
+
+    let mut inside_header = None;
+    if let Some(end) = tail.strip_prefix('[').and_then(|t| t.find(']')) {
+        inside_header = Some(&tail[1..end + 1]);
+        tail = &tail[end + 2..];
+    }
+
+    match tail.strip_prefix('(').and_then(|t| t.find(')')) {
+        Some(end) => {
+            let end_header = tail[end + 2..].trim();
+            (
+                name.trim(),
+                inside_header,
+                &tail[1..end + 1],
+                Some(end_header).filter(|header| !header.is_empty()),
+            )
+        }
+        None => (name.trim(), inside_header, "", None),
+    }
+}
+
+/// Affiliated Keywords
+///
+/// Keywords placed right above an element, without any blank line in between,
+/// e.g. `#+NAME:`, `#+CAPTION:` or `#+ATTR_HTML:`.
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Default, Clone)]
+pub struct AffiliatedKeywords<'a> {
+    /// Element name, from `#+NAME:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<Cow<'a, str>>,
+    /// Element captions, from `#+CAPTION:` or `#+CAPTION[SHORT]:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub caption: Vec<Caption<'a>>,
+    /// Header arguments, from `#+HEADER:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub header: Vec<Cow<'a, str>>,
+    /// Plot options, from `#+PLOT:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub plot: Option<Cow<'a, str>>,
+    /// Results marker, from `#+RESULTS:` or `#+RESULTS[HASH]:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub results: Option<Results<'a>>,
+    /// Export attributes, pairs of backend name and raw value from
+    /// `#+ATTR_BACKEND:`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub attributes: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    /// Raw keywords in order of appearance, with their original spelling,
+    /// which are written back as-is in org export
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub keywords: Vec<Keyword<'a>>,
+}
+
+/// Caption of an element
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct Caption<'a> {
+    /// Optional short caption
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub short: Option<Cow<'a, str>>,
+    /// Caption value
+    pub value: Cow<'a, str>,
+}
+
+/// Results marker of an element
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct Results<'a> {
+    /// Optional results hash
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub hash: Option<Cow<'a, str>>,
+    /// Results value, usually the name of the producing code
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> AffiliatedKeywords<'a> {
+    pub(crate) fn from_raw(keywords: &[RawKeyword<'a>]) -> AffiliatedKeywords<'a> {
+        let mut affiliated = AffiliatedKeywords::default();
+
+        for keyword in keywords {
+            let value = Cow::Borrowed(keyword.value);
+            match affiliated_key(keyword.key) {
+                Some(AffiliatedKey::Name) => affiliated.name = Some(value),
+                Some(AffiliatedKey::Caption) => affiliated.caption.push(Caption {
+                    short: keyword.optional.map(Into::into),
+                    value,
+                }),
+                Some(AffiliatedKey::Header) => affiliated.header.push(value),
+                Some(AffiliatedKey::Plot) => affiliated.plot = Some(value),
+                Some(AffiliatedKey::Results) => {
+                    affiliated.results = Some(Results {
+                        hash: keyword.optional.map(Into::into),
+                        value,
+                    })
+                }
+                Some(AffiliatedKey::Attr(backend)) => {
+                    affiliated.attributes.push((backend.into(), value))
+                }
+                None => (),
+            }
+            affiliated.keywords.push(Keyword {
+                key: keyword.key.into(),
+                optional: keyword.optional.map(Into::into),
+                value: keyword.value.into(),
+                post_blank: 0,
+            });
+        }
+
+        affiliated
+    }
+
+    /// Returns the export attributes of given backend as key-value pairs.
+    ///
+    /// ```rust
+    /// use orgize::{elements::AffiliatedKeywords, Element, Org};
+    ///
+    /// let org = Org::parse("#+ATTR_HTML: :width 50% :alt a cat\n[[./cat.png]]");
+    /// let affiliated = org
+    ///     .iter()
+    ///     .find_map(|event| match event {
+    ///         orgize::Event::Start(element) => element.affiliated(),
+    ///         _ => None,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     affiliated.attributes("html"),
+    ///     vec![("width", "50%"), ("alt", "a cat")]
+    /// );
+    /// assert!(affiliated.attributes("latex").is_empty());
+    /// ```
+    pub fn attributes(&self, backend: &str) -> Vec<(&str, &str)> {
+        let mut pairs = Vec::new();
+
+        for (_, value) in self
+            .attributes
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(backend))
+        {
+            let mut rest = value.trim();
+            while let Some(stripped) = rest.strip_prefix(':') {
+                let key_end = stripped.find(char::is_whitespace).unwrap_or(stripped.len());
+                let (key, tail) = stripped.split_at(key_end);
+                let value_end = tail.find(" :").map(|i| i + 1).unwrap_or_else(|| tail.len());
+                pairs.push((key, tail[0..value_end].trim()));
+                rest = tail[value_end..].trim_start();
+            }
+        }
+
+        pairs
+    }
+
+    /// Returns the first caption, or `None` if not set.
+    pub fn caption(&self) -> Option<&Caption<'a>> {
+        self.caption.first()
+    }
+
+    pub fn into_owned(self) -> AffiliatedKeywords<'static> {
+        AffiliatedKeywords {
+            name: self.name.map(Into::into).map(Cow::Owned),
+            caption: self
+                .caption
+                .into_iter()
+                .map(|caption| Caption {
+                    short: caption.short.map(Into::into).map(Cow::Owned),
+                    value: caption.value.into_owned().into(),
+                })
+                .collect(),
+            header: self
+                .header
+                .into_iter()
+                .map(|s| s.into_owned().into())
+                .collect(),
+            plot: self.plot.map(Into::into).map(Cow::Owned),
+            results: self.results.map(|results| Results {
+                hash: results.hash.map(Into::into).map(Cow::Owned),
+                value: results.value.into_owned().into(),
+            }),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(k, v)| (k.into_owned().into(), v.into_owned().into()))
+                .collect(),
+            keywords: self.keywords.into_iter().map(Keyword::into_owned).collect(),
         }
     }
 }
 
+enum AffiliatedKey<'a> {
+    Name,
+    Caption,
+    Header,
+    Plot,
+    Results,
+    Attr(&'a str),
+}
+
+fn affiliated_key(key: &str) -> Option<AffiliatedKey<'_>> {
+    let uppercase = key.to_ascii_uppercase();
+    match &*uppercase {
+        // including obsolete keywords translated by `org-element`
+        "NAME" | "DATA" | "LABEL" | "SOURCE" | "SRCNAME" | "TBLNAME" => Some(AffiliatedKey::Name),
+        "CAPTION" => Some(AffiliatedKey::Caption),
+        "HEADER" | "HEADERS" => Some(AffiliatedKey::Header),
+        "PLOT" => Some(AffiliatedKey::Plot),
+        "RESULTS" | "RESULT" => Some(AffiliatedKey::Results),
+        _ if uppercase.starts_with("ATTR_")
+            && key.len() > 5
+            && key[5..]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            Some(AffiliatedKey::Attr(&key[5..]))
+        }
+        _ => None,
+    }
+}
+
+/// Parses consecutive affiliated keywords, returns `None` if they are not
+/// directly followed by a non-blank line.
+pub(crate) fn parse_affiliated_keywords(input: &str) -> Option<(&str, Vec<RawKeyword<'_>>)> {
+    let mut keywords = Vec::new();
+    let mut tail = input;
+
+    while let Some((tail_, keyword)) = RawKeyword::parse(tail) {
+        if affiliated_key(keyword.key).is_none() {
+            break;
+        }
+        if keyword.post_blank > 0 {
+            return None;
+        }
+        keywords.push(keyword);
+        tail = tail_;
+    }
+
+    if keywords.is_empty() {
+        None
+    } else {
+        Some((tail, keywords))
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub(crate) struct RawKeyword<'a> {
@@ -85,6 +385,7 @@ impl<'a> RawKeyword<'a> {
             BabelCall {
                 value: value.into(),
                 post_blank,
+                affiliated: None,
             }
             .into()
         } else {
@@ -228,3 +529,30 @@ fn parse() {
         ))
     );
 }
+
+#[test]
+fn parse_affiliated() {
+    let (tail, keywords) = parse_affiliated_keywords(
+        "#+NAME: tbl\n#+CAPTION[Short]: Long caption.\n#+attr_html: :class data :border 1\n| a |",
+    )
+    .unwrap();
+    assert_eq!(tail, "| a |");
+
+    let affiliated = AffiliatedKeywords::from_raw(&keywords);
+    assert_eq!(affiliated.name, Some("tbl".into()));
+    assert_eq!(
+        affiliated.caption,
+        vec![Caption {
+            short: Some("Short".into()),
+            value: "Long caption.".into()
+        }]
+    );
+    assert_eq!(
+        affiliated.attributes("HTML"),
+        vec![("class", "data"), ("border", "1")]
+    );
+
+    assert!(parse_affiliated_keywords("#+TITLE: title\n| a |").is_none());
+    assert!(parse_affiliated_keywords("#+NAME: tbl\n\n| a |").is_none());
+    assert!(parse_affiliated_keywords("#+NAME: a\n#+CAPTION: b\n\n| a |").is_none());
+}
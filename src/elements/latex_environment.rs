@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::space0,
+    sequence::delimited,
+    IResult,
+};
+
+use crate::elements::AffiliatedKeywords;
+use crate::parse::combinators::{blank_lines_count, line, lines_till};
+
+/// LaTeX Environment Element
+///
+/// # Syntax
+///
+/// ```text
+/// \begin{NAME}ARGUMENTS
+/// CONTENTS
+/// \end{NAME}
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct LatexEnvironment<'a> {
+    /// Environment name, e.g. `equation` or `align*`
+    pub name: Cow<'a, str>,
+    /// Raw environment, from `\begin` line to `\end` line inclusive
+    pub value: Cow<'a, str>,
+    /// Numbers of blank lines between last environment's line and next
+    /// non-blank line or buffer's end
+    pub post_blank: usize,
+    /// Affiliated keywords attached to this environment
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
+}
+
+impl LatexEnvironment<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, LatexEnvironment<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    /// Returns the body of this environment, excluding its `\begin` and
+    /// `\end` lines.
+    pub fn contents(&self) -> &str {
+        let value = self.value.trim_end();
+        let start = value.find('\n').map(|i| i + 1).unwrap_or(value.len());
+        let end = value.rfind('\n').map(|i| i + 1).unwrap_or(value.len());
+        if start <= end {
+            &value[start..end]
+        } else {
+            ""
+        }
+    }
+
+    pub fn into_owned(self) -> LatexEnvironment<'static> {
+        LatexEnvironment {
+            name: self.name.into_owned().into(),
+            value: self.value.into_owned().into(),
+            post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
+}
+
+fn parse_internal(input: &str) -> IResult<&str, LatexEnvironment<'_>, ()> {
+    let (tail, _) = space0(input)?;
+    let (tail, name) = delimited(
+        tag("\\begin{"),
+        take_while1(|c: char| c.is_ascii_alphanumeric() || c == '*'),
+        tag("}"),
+    )(tail)?;
+    let (tail, _) = line(tail)?;
+    let end_line = format!("\\end{{{}}}", name);
+    let (tail, _) = lines_till(|line| line.trim() == end_line)(tail)?;
+    let value = &input[0..input.len() - tail.len()];
+    let (tail, post_blank) = blank_lines_count(tail)?;
+
+    Ok((
+        tail,
+        LatexEnvironment {
+            name: name.into(),
+            value: value.into(),
+            post_blank,
+            affiliated: None,
+        },
+    ))
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        LatexEnvironment::parse(
+            "\\begin{align*}\n2x - 5y &= 8 \\\\\n3x + 9y &= -12\n\\end{align*}\n\n"
+        ),
+        Some((
+            "",
+            LatexEnvironment {
+                name: "align*".into(),
+                value: "\\begin{align*}\n2x - 5y &= 8 \\\\\n3x + 9y &= -12\n\\end{align*}\n".into(),
+                post_blank: 1,
+                affiliated: None,
+            }
+        ))
+    );
+    assert_eq!(
+        LatexEnvironment::parse("  \\begin{equation}\nx\n  \\end{equation}")
+            .map(|(_, env)| env.contents().to_string()),
+        Some("x\n".into())
+    );
+    assert!(LatexEnvironment::parse("\\begin{equation}\nx\n\\end{align}").is_none());
+    assert!(LatexEnvironment::parse("\\begin{}\n\\end{}").is_none());
+}
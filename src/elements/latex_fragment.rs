@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use memchr::memchr;
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag, take_until, take_while1},
+    combinator::{opt, recognize},
+    multi::many0,
+    sequence::{delimited, tuple},
+    Err, IResult,
+};
+
+/// LaTeX Fragment Object
+///
+/// # Syntax
+///
+/// ```text
+/// \NAME BRACKETS
+/// \(CONTENTS\)
+/// \[CONTENTS\]
+/// $$CONTENTS$$
+/// $CHAR$
+/// $BORDER1 BODY BORDER2$
+/// ```
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct LatexFragment<'a> {
+    /// Raw fragment, including its delimiters
+    pub value: Cow<'a, str>,
+}
+
+impl LatexFragment<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, LatexFragment<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    pub fn into_owned(self) -> LatexFragment<'static> {
+        LatexFragment {
+            value: self.value.into_owned().into(),
+        }
+    }
+}
+
+fn parse_internal(input: &str) -> IResult<&str, LatexFragment<'_>, ()> {
+    let (input, value) = alt((
+        recognize(delimited(tag("\\("), take_until("\\)"), tag("\\)"))),
+        recognize(delimited(tag("\\["), take_until("\\]"), tag("\\]"))),
+        recognize(delimited(tag("$$"), take_until("$$"), tag("$$"))),
+        recognize(tuple((
+            tag("\\"),
+            take_while1(|c: char| c.is_ascii_alphabetic()),
+            opt(tag("*")),
+            many0(alt((
+                delimited(tag("["), opt(is_not("][{}\n")), tag("]")),
+                delimited(tag("{"), opt(is_not("{}\n")), tag("}")),
+            ))),
+        ))),
+        dollar,
+    ))(input)?;
+
+    Ok((
+        input,
+        LatexFragment {
+            value: value.into(),
+        },
+    ))
+}
+
+// `$CHAR$` or `$BORDER1 BODY BORDER2$`
+fn dollar(input: &str) -> IResult<&str, &str, ()> {
+    if !input.starts_with('$') || input.starts_with("$$") {
+        return Err(Err::Error(()));
+    }
+
+    let end = memchr(b'$', &input.as_bytes()[1..]).ok_or(Err::Error(()))? + 1;
+    let body = &input[1..end];
+    let tail = &input[end + 1..];
+
+    let valid = match (body.chars().next(), body.chars().last()) {
+        (Some(first), _) if body.chars().count() == 1 => {
+            !first.is_whitespace() && !".,?;\"".contains(first)
+        }
+        (Some(first), Some(last)) => {
+            !first.is_whitespace()
+                && !".,;$".contains(first)
+                && !last.is_whitespace()
+                && !".,$".contains(last)
+        }
+        _ => false,
+    };
+
+    // must be followed by a punctuation, a whitespace or the end of line
+    let post = !tail.starts_with(|c: char| !c.is_whitespace() && !c.is_ascii_punctuation());
+
+    if valid && post {
+        Ok((tail, &input[0..end + 1]))
+    } else {
+        Err(Err::Error(()))
+    }
+}
+
+#[test]
+fn parse() {
+    let value = |input| LatexFragment::parse(input).map(|(tail, fragment)| (tail, fragment.value));
+
+    assert_eq!(value("\\(x^2\\) y"), Some((" y", "\\(x^2\\)".into())));
+    assert_eq!(value("\\[ a + b \\]"), Some(("", "\\[ a + b \\]".into())));
+    assert_eq!(value("$$ 1 + 1 $$."), Some((".", "$$ 1 + 1 $$".into())));
+    assert_eq!(value("$x$, y"), Some((", y", "$x$".into())));
+    assert_eq!(value("$x^2 + y$"), Some(("", "$x^2 + y$".into())));
+    assert_eq!(
+        value("\\foo[a]{b}{c} d"),
+        Some((" d", "\\foo[a]{b}{c}".into()))
+    );
+    assert_eq!(
+        value("\\enlargethispage*{2cm}"),
+        Some(("", "\\enlargethispage*{2cm}".into()))
+    );
+    assert_eq!(value("$5 and $10"), None);
+    assert_eq!(value("$x$y"), None);
+    assert_eq!(value("$ x$"), None);
+    assert_eq!(value("$.$"), None);
+    assert_eq!(value("\\(x"), None);
+}
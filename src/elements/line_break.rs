@@ -0,0 +1,39 @@
+use nom::{bytes::complete::tag, IResult};
+
+use crate::parse::combinators::eol;
+
+/// Parses a line break object, the character before it must not be a
+/// backslash and should be checked by the caller.
+///
+/// # Syntax
+///
+/// ```text
+/// \\SPACE
+/// ```
+///
+/// `SPACE` is zero or more tabs and spaces, followed by the end of line.
+///
+/// Only the backslashes are consumed, `SPACE` and the newline are left as
+/// text.
+#[inline]
+pub(crate) fn parse_line_break(input: &str) -> Option<&str> {
+    parse_internal(input).ok().map(|(tail, _)| tail)
+}
+
+#[inline]
+fn parse_internal(input: &str) -> IResult<&str, (), ()> {
+    let (input, _) = tag("\\\\")(input)?;
+    eol(input)?;
+    Ok((input, ()))
+}
+
+#[test]
+fn parse() {
+    assert_eq!(parse_line_break("\\\\\nnext"), Some("\nnext"));
+    assert_eq!(parse_line_break("\\\\  \t\nnext"), Some("  \t\nnext"));
+    assert_eq!(parse_line_break("\\\\"), Some(""));
+
+    assert!(parse_line_break("\\\\ next").is_none());
+    assert!(parse_line_break("\\\\\\\n").is_none());
+    assert!(parse_line_break("\\\n").is_none());
+}
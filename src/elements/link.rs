@@ -1,9 +1,9 @@
 use std::borrow::Cow;
 
 use nom::{
-    bytes::complete::{tag, take_while},
-    combinator::opt,
-    sequence::delimited,
+    bytes::complete::{tag, take_while, take_while1},
+    combinator::{opt, verify},
+    sequence::{delimited, terminated},
     IResult,
 };
 
@@ -16,24 +16,57 @@ pub struct Link<'a> {
     pub path: Cow<'a, str>,
     #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
     pub desc: Option<Cow<'a, str>>,
+    /// Link syntax
+    pub format: LinkFormat,
+}
+
+/// Link Syntax
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, Copy)]
+pub enum LinkFormat {
+    /// `[[PATH]]` or `[[PATH][DESCRIPTION]]`
+    Bracket,
+    /// `TYPE:PATH` in running text
+    Plain,
+    /// `<TYPE:PATH>`
+    Angle,
 }
 
 impl Link<'_> {
     #[inline]
-    pub(crate) fn parse(input: &str) -> Option<(&str, Link)> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, Link<'_>)> {
         parse_internal(input).ok()
     }
 
+    /// Parses a plain link whose type is one of `link_types`.
+    pub(crate) fn parse_plain<'a>(
+        input: &'a str,
+        link_types: &[String],
+    ) -> Option<(&'a str, Link<'a>)> {
+        parse_plain(input, link_types).ok()
+    }
+
+    /// Parses an angle link whose type is one of `link_types`.
+    pub(crate) fn parse_angle<'a>(
+        input: &'a str,
+        link_types: &[String],
+    ) -> Option<(&'a str, Link<'a>)> {
+        parse_angle(input, link_types).ok()
+    }
+
     pub fn into_owned(self) -> Link<'static> {
         Link {
             path: self.path.into_owned().into(),
             desc: self.desc.map(Into::into).map(Cow::Owned),
+            format: self.format,
         }
     }
 }
 
 #[inline]
-fn parse_internal(input: &str) -> IResult<&str, Link, ()> {
+fn parse_internal(input: &str) -> IResult<&str, Link<'_>, ()> {
     let (input, path) = delimited(
         tag("[["),
         take_while(|c: char| c != '<' && c != '>' && c != '\n' && c != ']'),
@@ -50,6 +83,69 @@ fn parse_internal(input: &str) -> IResult<&str, Link, ()> {
         Link {
             path: path.into(),
             desc: desc.map(Into::into),
+            format: LinkFormat::Bracket,
+        },
+    ))
+}
+
+fn link_type<'a>(input: &'a str, link_types: &[String]) -> IResult<&'a str, &'a str, ()> {
+    terminated(
+        verify(
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '+' || c == '-'),
+            |ty: &str| link_types.iter().any(|t| t == ty),
+        ),
+        tag(":"),
+    )(input)
+}
+
+fn parse_plain<'a>(input: &'a str, link_types: &[String]) -> IResult<&'a str, Link<'a>, ()> {
+    let (tail, _) = link_type(input, link_types)?;
+    let (tail, path) =
+        take_while1(|c: char| !c.is_ascii_whitespace() && !"[]()<>".contains(c))(tail)?;
+
+    // path may end with a parenthesized word, e.g. `Foo_(bar)`
+    let len = if let Ok((_, word)) = delimited(
+        tag::<_, _, ()>("("),
+        take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+        tag(")"),
+    )(tail)
+    {
+        path.len() + word.len() + 2
+    } else {
+        // otherwise it can't end with a punctuation other than slash
+        path.char_indices()
+            .rev()
+            .find(|&(_, c)| c == '/' || !c.is_ascii_punctuation())
+            .map(|(i, c)| i + c.len_utf8())
+            .filter(|&len| len > 0)
+            .ok_or(nom::Err::Error(()))?
+    };
+
+    let end = input.len() - tail.len() - path.len() + len;
+
+    Ok((
+        &input[end..],
+        Link {
+            path: input[0..end].into(),
+            desc: None,
+            format: LinkFormat::Plain,
+        },
+    ))
+}
+
+fn parse_angle<'a>(input: &'a str, link_types: &[String]) -> IResult<&'a str, Link<'a>, ()> {
+    let (tail, _) = tag("<")(input)?;
+    let (path_tail, _) = link_type(tail, link_types)?;
+    let (path_tail, _) = take_while(|c: char| c != '>' && c != '<' && c != '\n')(path_tail)?;
+    let path = &tail[0..tail.len() - path_tail.len()];
+    let (tail, _) = tag(">")(path_tail)?;
+
+    Ok((
+        tail,
+        Link {
+            path: path.into(),
+            desc: None,
+            format: LinkFormat::Angle,
         },
     ))
 }
@@ -62,7 +158,8 @@ fn parse() {
             "",
             Link {
                 path: "#id".into(),
-                desc: None
+                desc: None,
+                format: LinkFormat::Bracket,
             }
         ))
     );
@@ -72,9 +169,52 @@ fn parse() {
             "",
             Link {
                 path: "#id".into(),
-                desc: Some("desc".into())
+                desc: Some("desc".into()),
+                format: LinkFormat::Bracket,
             }
         ))
     );
     assert!(Link::parse("[[#id][desc]").is_none());
 }
+
+#[test]
+fn parse_plain_and_angle() {
+    let types = vec![
+        "https".to_string(),
+        "mailto".to_string(),
+        "file+sys".to_string(),
+    ];
+    let plain = |input| Link::parse_plain(input, &types).map(|(tail, link)| (tail, link.path));
+    let angle = |input| Link::parse_angle(input, &types).map(|(tail, link)| (tail, link.path));
+
+    assert_eq!(
+        plain("https://example.com/x. Next"),
+        Some((". Next", "https://example.com/x".into()))
+    );
+    assert_eq!(
+        plain("https://example.com/"),
+        Some(("", "https://example.com/".into()))
+    );
+    assert_eq!(
+        plain("https://en.wikipedia.org/wiki/Foo_(bar)),"),
+        Some(("),", "https://en.wikipedia.org/wiki/Foo_(bar)".into()))
+    );
+    assert_eq!(
+        plain("file+sys:/tmp/a.txt"),
+        Some(("", "file+sys:/tmp/a.txt".into()))
+    );
+    assert_eq!(plain("ftp://example.com"), None);
+    assert_eq!(plain("https:"), None);
+    assert_eq!(plain("https:..."), None);
+
+    assert_eq!(
+        angle("<mailto:foo@bar> x"),
+        Some((" x", "mailto:foo@bar".into()))
+    );
+    assert_eq!(
+        Link::parse_angle("<https://a b>", &types).map(|(_, link)| link.format),
+        Some(LinkFormat::Angle)
+    );
+    assert_eq!(angle("<ftp://foo>"), None);
+    assert_eq!(angle("<mailto:foo"), None);
+}
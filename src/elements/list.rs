@@ -1,28 +1,36 @@
 use std::borrow::Cow;
-use std::iter::once;
 
 use memchr::{memchr, memchr_iter};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{digit1, space0},
-    combinator::{map, recognize},
-    sequence::terminated,
+    bytes::complete::{tag, tag_no_case, take_while_m_n},
+    character::complete::{alpha1, digit1, space0, space1},
+    combinator::{map, opt, recognize, value},
+    sequence::{delimited, preceded, terminated},
     IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
+use crate::parse::combinators::{line, lines_till};
+
 /// Plain List Element
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
 #[derive(Debug, Clone)]
-pub struct List {
+pub struct List<'a> {
     /// List indent, number of whitespaces
     pub indent: usize,
     /// List's type, determined by the first item of this list
     pub ordered: bool,
+    /// Whether this list is a description list, i.e. its first item is
+    /// unordered and has a tag
+    pub description: bool,
     /// Numbers of blank lines between last list's line and next non-blank line
     /// or buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this list
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
 /// List Item Element
@@ -32,19 +40,66 @@ pub struct List {
 pub struct ListItem<'a> {
     /// List item bullet
     pub bullet: Cow<'a, str>,
-    /// List item indent, number of whitespaces
+    /// List item indent, column of the bullet with tabs expanded to
+    /// [`ParseConfig::tab_width`](crate::ParseConfig::tab_width)
     pub indent: usize,
     /// List item type
     pub ordered: bool,
-    // TODO checkbox
-    // TODO counter
-    // TODO tag
+    /// List item counter set, e.g. `5` in `[@5]`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub counter: Option<Cow<'a, str>>,
+    /// List item checkbox
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub checkbox: Option<Checkbox>,
+    /// List item tag, e.g. `term` in `- term :: description`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub tag: Option<Cow<'a, str>>,
+    /// Raw item header, from the bullet to the beginning of contents, e.g.
+    /// `- [x] term  :: `, which is written back as-is in org export
+    pub header: Cow<'a, str>,
+}
+
+/// List Item Checkbox
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, Copy)]
+pub enum Checkbox {
+    /// `[X]`
+    On,
+    /// `[ ]`
+    Off,
+    /// `[-]`
+    Trans,
+}
+
+impl Checkbox {
+    /// Returns the character inside the brackets of this checkbox.
+    pub fn as_char(self) -> char {
+        match self {
+            Checkbox::On => 'X',
+            Checkbox::Off => ' ',
+            Checkbox::Trans => '-',
+        }
+    }
+}
+
+impl List<'_> {
+    pub fn into_owned(self) -> List<'static> {
+        List {
+            indent: self.indent,
+            ordered: self.ordered,
+            description: self.description,
+            post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
 }
 
 impl ListItem<'_> {
     #[inline]
-    pub(crate) fn parse(input: &str) -> Option<(&str, (ListItem, &str))> {
-        list_item(input).ok()
+    pub(crate) fn parse(input: &str, tab_width: usize) -> Option<(&str, (ListItem, &str))> {
+        list_item(input, tab_width).ok()
     }
 
     pub fn into_owned(self) -> ListItem<'static> {
@@ -52,79 +107,163 @@ impl ListItem<'_> {
             bullet: self.bullet.into_owned().into(),
             indent: self.indent,
             ordered: self.ordered,
+            counter: self.counter.map(Into::into).map(Cow::Owned),
+            checkbox: self.checkbox,
+            tag: self.tag.map(Into::into).map(Cow::Owned),
+            header: self.header.into_owned().into(),
         }
     }
 }
 
-fn list_item(input: &str) -> IResult<&str, (ListItem, &str), ()> {
-    let (input, indent) = map(space0, |s: &str| s.len())(input)?;
+fn list_item(input: &str, tab_width: usize) -> IResult<&str, (ListItem, &str), ()> {
+    let (input, indent) = map(space0, |s: &str| indent_width(s, tab_width))(input)?;
+    let start = input;
     let (input, bullet) = recognize(alt((
         tag("+ "),
         tag("* "),
         tag("- "),
         terminated(digit1, tag(". ")),
+        terminated(digit1, tag(") ")),
     )))(input)?;
-    let (input, contents) = list_item_contents(input, indent);
+    let ordered = bullet.starts_with(|c: char| c.is_ascii_digit());
+    let (input, counter) = opt(terminated(
+        delimited(
+            tag("[@"),
+            alt((
+                digit1,
+                take_while_m_n(1, 1, |c: char| c.is_ascii_alphabetic()),
+            )),
+            tag("]"),
+        ),
+        space1,
+    ))(input)?;
+    let (input, checkbox) = opt(terminated(
+        alt((
+            value(Checkbox::On, alt((tag("[X]"), tag("[x]")))),
+            value(Checkbox::Off, tag("[ ]")),
+            value(Checkbox::Trans, tag("[-]")),
+        )),
+        space1,
+    ))(input)?;
+    let (input, item_tag) = if ordered {
+        (input, None)
+    } else {
+        list_item_tag(input)
+    };
+    let header = &start[0..start.len() - input.len()];
+    let (input, contents) = list_item_contents(input, indent, tab_width);
     Ok((
         input,
         (
             ListItem {
                 bullet: bullet.into(),
                 indent,
-                ordered: bullet.starts_with(|c: char| c.is_ascii_digit()),
+                ordered,
+                counter: counter.map(Into::into),
+                checkbox,
+                tag: item_tag.map(Into::into),
+                header: header.into(),
             },
             contents,
         ),
     ))
 }
 
-fn list_item_contents(input: &str, indent: usize) -> (&str, &str) {
-    let mut last_end = memchr(b'\n', input.as_bytes())
-        .map(|i| i + 1)
-        .unwrap_or_else(|| input.len());
-
-    for i in memchr_iter(b'\n', input.as_bytes())
-        .map(|i| i + 1)
-        .chain(once(input.len()))
-        .skip(1)
-    {
-        if input[last_end..i]
-            .as_bytes()
-            .iter()
-            .all(u8::is_ascii_whitespace)
-        {
-            let x = memchr(b'\n', &input[i..].as_bytes())
-                .map(|ii| i + ii + 1)
-                .unwrap_or_else(|| input.len());
-
-            // two consecutive empty lines
-            if input[i..x].as_bytes().iter().all(u8::is_ascii_whitespace) {
-                return (&input[x..], &input[0..x]);
+// `TAG :: ` on the first line of an unordered list item
+fn list_item_tag(input: &str) -> (&str, Option<&str>) {
+    let line_end = memchr(b'\n', input.as_bytes()).unwrap_or(input.len());
+    let line = &input[0..line_end];
+
+    for i in memchr_iter(b':', line.as_bytes()) {
+        let tail = &line[i..];
+        if !tail.starts_with("::") || !line[0..i].ends_with(&[' ', '\t'][..]) {
+            continue;
+        }
+        let after = &tail[2..];
+        if !after.is_empty() && !after.starts_with(&[' ', '\t'][..]) {
+            continue;
+        }
+        let tag = line[0..i].trim_end();
+        if tag.is_empty() {
+            continue;
+        }
+        let rest = &input[i + 2..];
+        return (rest.trim_start_matches(&[' ', '\t'][..]), Some(tag));
+    }
+
+    (input, None)
+}
+
+fn list_item_contents(input: &str, indent: usize, tab_width: usize) -> (&str, &str) {
+    // the first line always belongs to the item
+    let mut pos = next_line(input, 0);
+
+    while pos < input.len() {
+        let end = next_line(input, pos);
+
+        if is_blank(&input[pos..end]) {
+            // two consecutive blank lines end the whole list
+            if end < input.len() && is_blank(&input[end..next_line(input, end)]) {
+                return (&input[pos..], &input[0..pos]);
             }
+            pos = end;
+            continue;
         }
 
-        // line less or equally indented than the starting line
-        if input[last_end..i]
-            .as_bytes()
-            .iter()
-            .take(indent + 1)
-            .any(|c| !c.is_ascii_whitespace())
-        {
-            return (&input[last_end..], &input[0..last_end]);
+        // line less or equally indented than the bullet
+        if indent_width(&input[pos..end], tab_width) <= indent {
+            return (&input[pos..], &input[0..pos]);
         }
 
-        last_end = i;
+        // lines inside a block never end the item
+        pos = block_end(&input[pos..]).map_or(end, |tail| input.len() - tail.len());
     }
 
     ("", input)
 }
 
+fn next_line(input: &str, pos: usize) -> usize {
+    memchr(b'\n', &input.as_bytes()[pos..])
+        .map(|i| pos + i + 1)
+        .unwrap_or(input.len())
+}
+
+fn is_blank(line: &str) -> bool {
+    line.as_bytes().iter().all(u8::is_ascii_whitespace)
+}
+
+// column of the first non-whitespace character, with tabs expanded
+fn indent_width(input: &str, tab_width: usize) -> usize {
+    let tab_width = tab_width.max(1);
+    input
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .fold(0, |col, c| {
+            if c == '\t' {
+                (col / tab_width + 1) * tab_width
+            } else {
+                col + 1
+            }
+        })
+}
+
+// returns the input after the `#+END_` line if input starts with a block
+fn block_end(input: &str) -> Option<&str> {
+    let (input, _) = space0::<_, ()>(input).ok()?;
+    let (input, name) = preceded(tag_no_case("#+BEGIN_"), alpha1::<_, ()>)(input).ok()?;
+    let (input, _) = line(input).ok()?;
+    let end_line = format!("#+END_{}", name);
+    let (input, _) = lines_till(|line| line.trim().eq_ignore_ascii_case(&end_line))(input).ok()?;
+    Some(input)
+}
+
 #[test]
 fn parse() {
     assert_eq!(
         list_item(
             r#"+ item1
-+ item2"#
++ item2"#,
+            8
         ),
         Ok((
             "+ item2",
@@ -133,6 +272,10 @@ fn parse() {
                     bullet: "+ ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "+ ".into(),
                 },
                 r#"item1
 "#
@@ -143,7 +286,8 @@ fn parse() {
         list_item(
             r#"* item1
 
-* item2"#
+* item2"#,
+            8
         ),
         Ok((
             "* item2",
@@ -152,6 +296,10 @@ fn parse() {
                     bullet: "* ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "* ".into(),
                 },
                 r#"item1
 
@@ -164,19 +312,22 @@ fn parse() {
             r#"* item1
 
 
-* item2"#
+* item2"#,
+            8
         ),
         Ok((
-            "* item2",
+            "\n\n* item2",
             (
                 ListItem {
                     bullet: "* ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "* ".into(),
                 },
                 r#"item1
-
-
 "#
             )
         ))
@@ -185,7 +336,8 @@ fn parse() {
         list_item(
             r#"* item1
 
-"#
+"#,
+            8
         ),
         Ok((
             "",
@@ -194,6 +346,10 @@ fn parse() {
                     bullet: "* ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "* ".into(),
                 },
                 r#"item1
 
@@ -205,7 +361,8 @@ fn parse() {
         list_item(
             r#"+ item1
   + item2
-"#
+"#,
+            8
         ),
         Ok((
             "",
@@ -214,6 +371,10 @@ fn parse() {
                     bullet: "+ ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "+ ".into(),
                 },
                 r#"item1
   + item2
@@ -227,7 +388,8 @@ fn parse() {
 
   + item2
 
-+ item 3"#
++ item 3"#,
+            8
         ),
         Ok((
             "+ item 3",
@@ -236,6 +398,10 @@ fn parse() {
                     bullet: "+ ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "+ ".into(),
                 },
                 r#"item1
 
@@ -249,7 +415,8 @@ fn parse() {
         list_item(
             r#"  + item1
 
-  + item2"#
+  + item2"#,
+            8
         ),
         Ok((
             "  + item2",
@@ -258,6 +425,10 @@ fn parse() {
                     bullet: "+ ".into(),
                     indent: 2,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "+ ".into(),
                 },
                 r#"item1
 
@@ -269,7 +440,8 @@ fn parse() {
         list_item(
             r#"  1. item1
 2. item2
-  3. item3"#
+  3. item3"#,
+            8
         ),
         Ok((
             r#"2. item2
@@ -279,6 +451,10 @@ fn parse() {
                     bullet: "1. ".into(),
                     indent: 2,
                     ordered: true,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "1. ".into(),
                 },
                 r#"item1
 "#
@@ -293,7 +469,8 @@ fn parse() {
 
   - 3
 
-+ 4"#
++ 4"#,
+            8
         ),
         Ok((
             "+ 4",
@@ -302,6 +479,10 @@ fn parse() {
                     bullet: "+ ".into(),
                     indent: 0,
                     ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "+ ".into(),
                 },
                 r#"1
 
@@ -314,3 +495,134 @@ fn parse() {
         ))
     );
 }
+
+#[test]
+fn parse_item_prefixes() {
+    assert_eq!(
+        list_item("- [@5] [X] done\n", 8),
+        Ok((
+            "",
+            (
+                ListItem {
+                    bullet: "- ".into(),
+                    indent: 0,
+                    ordered: false,
+                    counter: Some("5".into()),
+                    checkbox: Some(Checkbox::On),
+                    tag: None,
+                    header: "- [@5] [X] ".into(),
+                },
+                "done\n"
+            )
+        ))
+    );
+    assert_eq!(
+        list_item("1. [-] partial", 8),
+        Ok((
+            "",
+            (
+                ListItem {
+                    bullet: "1. ".into(),
+                    indent: 0,
+                    ordered: true,
+                    counter: None,
+                    checkbox: Some(Checkbox::Trans),
+                    tag: None,
+                    header: "1. [-] ".into(),
+                },
+                "partial"
+            )
+        ))
+    );
+    assert_eq!(
+        list_item("+ [ ] term :: description", 8),
+        Ok((
+            "",
+            (
+                ListItem {
+                    bullet: "+ ".into(),
+                    indent: 0,
+                    ordered: false,
+                    counter: None,
+                    checkbox: Some(Checkbox::Off),
+                    tag: Some("term".into()),
+                    header: "+ [ ] term :: ".into(),
+                },
+                "description"
+            )
+        ))
+    );
+    assert_eq!(
+        list_item("- a::b :: c", 8),
+        Ok((
+            "",
+            (
+                ListItem {
+                    bullet: "- ".into(),
+                    indent: 0,
+                    ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: Some("a::b".into()),
+                    header: "- a::b :: ".into(),
+                },
+                "c"
+            )
+        ))
+    );
+    assert_eq!(
+        list_item("- [X]text", 8),
+        Ok((
+            "",
+            (
+                ListItem {
+                    bullet: "- ".into(),
+                    indent: 0,
+                    ordered: false,
+                    counter: None,
+                    checkbox: None,
+                    tag: None,
+                    header: "- ".into(),
+                },
+                "[X]text"
+            )
+        ))
+    );
+}
+
+#[test]
+fn parse_indentation() {
+    // tabs are expanded to the tab width
+    assert_eq!(
+        list_item("\t- item1\n\t  text\n        - item2", 8).map(|(tail, (item, contents))| (
+            tail,
+            item.indent,
+            contents
+        )),
+        Ok(("        - item2", 8, "item1\n\t  text\n"))
+    );
+    assert_eq!(
+        list_item("\t- item1\n    - item2", 4).map(|(tail, (item, contents))| (
+            tail,
+            item.indent,
+            contents
+        )),
+        Ok(("    - item2", 4, "item1\n"))
+    );
+    // lines inside blocks never end the item
+    assert_eq!(
+        list_item(
+            "- item\n  #+BEGIN_EXAMPLE\ntext\n\n\n  #+END_EXAMPLE\n  more\nend",
+            8
+        )
+        .map(|(tail, (_, contents))| (tail, contents)),
+        Ok((
+            "end",
+            "item\n  #+BEGIN_EXAMPLE\ntext\n\n\n  #+END_EXAMPLE\n  more\n"
+        ))
+    );
+    assert_eq!(
+        list_item("1) item\n2) item", 8).map(|(tail, (item, _))| (tail, item.ordered)),
+        Ok(("2) item", true))
+    );
+}
@@ -1,24 +1,33 @@
 //! Org-mode elements
 
 pub(crate) mod block;
+pub(crate) mod citation;
 pub(crate) mod clock;
 pub(crate) mod comment;
 pub(crate) mod cookie;
+pub(crate) mod diary_sexp;
 pub(crate) mod drawer;
 pub(crate) mod dyn_block;
 pub(crate) mod emphasis;
+pub(crate) mod entity;
 pub(crate) mod fixed_width;
 pub(crate) mod fn_def;
 pub(crate) mod fn_ref;
+pub(crate) mod header_args;
 pub(crate) mod inline_call;
 pub(crate) mod inline_src;
+pub(crate) mod inlinetask;
 pub(crate) mod keyword;
+pub(crate) mod latex_environment;
+pub(crate) mod latex_fragment;
+pub(crate) mod line_break;
 pub(crate) mod link;
 pub(crate) mod list;
 pub(crate) mod macros;
 pub(crate) mod planning;
 pub(crate) mod radio_target;
 pub(crate) mod rule;
+pub(crate) mod script;
 pub(crate) mod snippet;
 pub(crate) mod table;
 pub(crate) mod target;
@@ -27,29 +36,37 @@ pub(crate) mod title;
 
 pub use self::{
     block::{
-        CenterBlock, CommentBlock, ExampleBlock, ExportBlock, QuoteBlock, SourceBlock,
-        SpecialBlock, VerseBlock,
+        BlockSwitches, CenterBlock, CommentBlock, ExampleBlock, ExportBlock, NumberLines,
+        QuoteBlock, SourceBlock, SpecialBlock, VerseBlock,
     },
+    citation::{Citation, CitationReference},
     clock::Clock,
     comment::Comment,
     cookie::Cookie,
+    diary_sexp::DiarySexp,
     drawer::Drawer,
     dyn_block::DynBlock,
+    entity::{Entity, EntityDef},
     fixed_width::FixedWidth,
     fn_def::FnDef,
     fn_ref::FnRef,
+    header_args::{Exports, HeaderArgs, HeaderResults, Noweb, Tangle},
     inline_call::InlineCall,
     inline_src::InlineSrc,
-    keyword::{BabelCall, Keyword},
-    link::Link,
-    list::{List, ListItem},
+    inlinetask::Inlinetask,
+    keyword::{AffiliatedKeywords, BabelCall, Caption, Keyword, Results},
+    latex_environment::LatexEnvironment,
+    latex_fragment::LatexFragment,
+    link::{Link, LinkFormat},
+    list::{Checkbox, List, ListItem},
     macros::Macros,
     planning::Planning,
+    radio_target::{RadioLink, RadioTarget},
     rule::Rule,
     snippet::Snippet,
-    table::{Table, TableCell, TableRow},
+    table::{ColumnAlign, ColumnGroup, Table, TableCell, TableColumn, TableElCell, TableRow},
     target::Target,
-    timestamp::{Datetime, Timestamp},
+    timestamp::{Datetime, Delay, DelayType, Repeater, RepeaterType, TimeUnit, Timestamp},
     title::{PropertiesMap, Title},
 };
 
@@ -70,35 +87,74 @@ pub enum Element<'a> {
     SourceBlock(SourceBlock<'a>),
     BabelCall(BabelCall<'a>),
     Section,
+    Citation(Citation<'a>),
     Clock(Clock<'a>),
     Cookie(Cookie<'a>),
-    RadioTarget,
+    RadioTarget(RadioTarget<'a>),
+    RadioLink(RadioLink<'a>),
     Drawer(Drawer<'a>),
-    Document { pre_blank: usize },
+    Document {
+        /// Numbers of blank lines before document contents, after the
+        /// property drawer if any
+        pre_blank: usize,
+        /// Numbers of blank lines before the property drawer at the
+        /// beginning of document
+        properties_pre_blank: usize,
+        /// Properties from the property drawer at the beginning of document
+        #[cfg_attr(
+            feature = "ser",
+            serde(skip_serializing_if = "PropertiesMap::is_empty")
+        )]
+        properties: PropertiesMap<'a>,
+    },
     DynBlock(DynBlock<'a>),
+    Entity(Entity<'a>),
     FnDef(FnDef<'a>),
     FnRef(FnRef<'a>),
-    Headline { level: usize },
+    Headline {
+        level: usize,
+    },
     InlineCall(InlineCall<'a>),
     InlineSrc(InlineSrc<'a>),
+    Inlinetask(Inlinetask),
     Keyword(Keyword<'a>),
+    LatexEnvironment(LatexEnvironment<'a>),
+    LatexFragment(LatexFragment<'a>),
     Link(Link<'a>),
-    List(List),
+    List(List<'a>),
     ListItem(ListItem<'a>),
     Macros(Macros<'a>),
     Snippet(Snippet<'a>),
-    Text { value: Cow<'a, str> },
-    Paragraph { post_blank: usize },
-    Rule(Rule),
+    Text {
+        value: Cow<'a, str>,
+    },
+    Paragraph {
+        post_blank: usize,
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        affiliated: Option<Box<AffiliatedKeywords<'a>>>,
+    },
+    Rule(Rule<'a>),
     Timestamp(Timestamp<'a>),
     Target(Target<'a>),
+    LineBreak,
     Bold,
     Strike,
     Italic,
     Underline,
-    Verbatim { value: Cow<'a, str> },
-    Code { value: Cow<'a, str> },
+    Subscript {
+        brackets: bool,
+    },
+    Superscript {
+        brackets: bool,
+    },
+    Verbatim {
+        value: Cow<'a, str>,
+    },
+    Code {
+        value: Cow<'a, str>,
+    },
     Comment(Comment<'a>),
+    DiarySexp(DiarySexp<'a>),
     FixedWidth(FixedWidth<'a>),
     Title(Title<'a>),
     Table(Table<'a>),
@@ -106,7 +162,7 @@ pub enum Element<'a> {
     TableCell(TableCell),
 }
 
-impl Element<'_> {
+impl<'a> Element<'a> {
     pub fn is_container(&self) -> bool {
         match self {
             Element::SpecialBlock(_)
@@ -117,6 +173,7 @@ impl Element<'_> {
             | Element::Document { .. }
             | Element::DynBlock(_)
             | Element::Headline { .. }
+            | Element::Inlinetask(_)
             | Element::Italic
             | Element::List(_)
             | Element::ListItem(_)
@@ -124,6 +181,8 @@ impl Element<'_> {
             | Element::Section
             | Element::Strike
             | Element::Underline
+            | Element::Subscript { .. }
+            | Element::Superscript { .. }
             | Element::Title(_)
             | Element::Table(_)
             | Element::TableRow(TableRow::Header)
@@ -133,6 +192,60 @@ impl Element<'_> {
         }
     }
 
+    /// Returns the affiliated keywords attached to this element, or `None`
+    /// if it has none or doesn't accept any.
+    pub fn affiliated(&self) -> Option<&AffiliatedKeywords<'a>> {
+        match self {
+            Element::SpecialBlock(SpecialBlock { affiliated, .. })
+            | Element::QuoteBlock(QuoteBlock { affiliated, .. })
+            | Element::CenterBlock(CenterBlock { affiliated, .. })
+            | Element::VerseBlock(VerseBlock { affiliated, .. })
+            | Element::CommentBlock(CommentBlock { affiliated, .. })
+            | Element::ExampleBlock(ExampleBlock { affiliated, .. })
+            | Element::ExportBlock(ExportBlock { affiliated, .. })
+            | Element::SourceBlock(SourceBlock { affiliated, .. })
+            | Element::BabelCall(BabelCall { affiliated, .. })
+            | Element::DiarySexp(DiarySexp { affiliated, .. })
+            | Element::FixedWidth(FixedWidth { affiliated, .. })
+            | Element::LatexEnvironment(LatexEnvironment { affiliated, .. })
+            | Element::Paragraph { affiliated, .. }
+            | Element::Table(Table::Org { affiliated, .. })
+            | Element::Table(Table::TableEl { affiliated, .. })
+            | Element::List(List { affiliated, .. })
+            | Element::Drawer(Drawer { affiliated, .. })
+            | Element::DynBlock(DynBlock { affiliated, .. })
+            | Element::FnDef(FnDef { affiliated, .. })
+            | Element::Rule(Rule { affiliated, .. }) => affiliated.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn affiliated_mut(&mut self) -> Option<&mut Option<Box<AffiliatedKeywords<'a>>>> {
+        match self {
+            Element::SpecialBlock(SpecialBlock { affiliated, .. })
+            | Element::QuoteBlock(QuoteBlock { affiliated, .. })
+            | Element::CenterBlock(CenterBlock { affiliated, .. })
+            | Element::VerseBlock(VerseBlock { affiliated, .. })
+            | Element::CommentBlock(CommentBlock { affiliated, .. })
+            | Element::ExampleBlock(ExampleBlock { affiliated, .. })
+            | Element::ExportBlock(ExportBlock { affiliated, .. })
+            | Element::SourceBlock(SourceBlock { affiliated, .. })
+            | Element::BabelCall(BabelCall { affiliated, .. })
+            | Element::DiarySexp(DiarySexp { affiliated, .. })
+            | Element::FixedWidth(FixedWidth { affiliated, .. })
+            | Element::LatexEnvironment(LatexEnvironment { affiliated, .. })
+            | Element::Paragraph { affiliated, .. }
+            | Element::Table(Table::Org { affiliated, .. })
+            | Element::Table(Table::TableEl { affiliated, .. })
+            | Element::List(List { affiliated, .. })
+            | Element::Drawer(Drawer { affiliated, .. })
+            | Element::DynBlock(DynBlock { affiliated, .. })
+            | Element::FnDef(FnDef { affiliated, .. })
+            | Element::Rule(Rule { affiliated, .. }) => Some(affiliated),
+            _ => None,
+        }
+    }
+
     pub fn into_owned(self) -> Element<'static> {
         use Element::*;
 
@@ -147,34 +260,57 @@ impl Element<'_> {
             SourceBlock(e) => SourceBlock(e.into_owned()),
             BabelCall(e) => BabelCall(e.into_owned()),
             Section => Section,
+            Citation(e) => Citation(e.into_owned()),
             Clock(e) => Clock(e.into_onwed()),
             Cookie(e) => Cookie(e.into_owned()),
-            RadioTarget => RadioTarget,
+            RadioTarget(e) => RadioTarget(e.into_owned()),
+            RadioLink(e) => RadioLink(e.into_owned()),
             Drawer(e) => Drawer(e.into_owned()),
-            Document { pre_blank } => Document { pre_blank },
+            Document {
+                pre_blank,
+                properties_pre_blank,
+                properties,
+            } => Document {
+                pre_blank,
+                properties_pre_blank,
+                properties: properties.into_owned(),
+            },
             DynBlock(e) => DynBlock(e.into_owned()),
+            Entity(e) => Entity(e.into_owned()),
             FnDef(e) => FnDef(e.into_owned()),
             FnRef(e) => FnRef(e.into_owned()),
             Headline { level } => Headline { level },
             InlineCall(e) => InlineCall(e.into_owned()),
             InlineSrc(e) => InlineSrc(e.into_owned()),
+            Inlinetask(e) => Inlinetask(e),
             Keyword(e) => Keyword(e.into_owned()),
+            LatexEnvironment(e) => LatexEnvironment(e.into_owned()),
+            LatexFragment(e) => LatexFragment(e.into_owned()),
             Link(e) => Link(e.into_owned()),
-            List(e) => List(e),
+            List(e) => List(e.into_owned()),
             ListItem(e) => ListItem(e.into_owned()),
             Macros(e) => Macros(e.into_owned()),
             Snippet(e) => Snippet(e.into_owned()),
             Text { value } => Text {
                 value: value.into_owned().into(),
             },
-            Paragraph { post_blank } => Paragraph { post_blank },
-            Rule(e) => Rule(e),
+            Paragraph {
+                post_blank,
+                affiliated,
+            } => Paragraph {
+                post_blank,
+                affiliated: affiliated.map(|a| Box::new(a.into_owned())),
+            },
+            Rule(e) => Rule(e.into_owned()),
             Timestamp(e) => Timestamp(e.into_owned()),
             Target(e) => Target(e.into_owned()),
+            LineBreak => LineBreak,
             Bold => Bold,
             Strike => Strike,
             Italic => Italic,
             Underline => Underline,
+            Subscript { brackets } => Subscript { brackets },
+            Superscript { brackets } => Superscript { brackets },
             Verbatim { value } => Verbatim {
                 value: value.into_owned().into(),
             },
@@ -182,6 +318,7 @@ impl Element<'_> {
                 value: value.into_owned().into(),
             },
             Comment(e) => Comment(e.into_owned()),
+            DiarySexp(e) => DiarySexp(e.into_owned()),
             FixedWidth(e) => FixedWidth(e.into_owned()),
             Title(e) => Title(e.into_owned()),
             Table(e) => Table(e.into_owned()),
@@ -213,12 +350,15 @@ macro_rules! impl_from {
 impl_from!(
     BabelCall,
     CenterBlock,
+    Citation,
     Clock,
     Comment,
     CommentBlock,
     Cookie,
+    DiarySexp,
     Drawer,
     DynBlock,
+    Entity,
     ExampleBlock,
     ExportBlock,
     FixedWidth,
@@ -227,10 +367,16 @@ impl_from!(
     InlineCall,
     InlineSrc,
     Keyword,
+    LatexEnvironment,
+    LatexFragment,
     Link,
+    List,
     ListItem,
     Macros,
     QuoteBlock,
+    RadioLink,
+    RadioTarget,
+    Rule,
     Snippet,
     SourceBlock,
     SpecialBlock,
@@ -239,7 +385,6 @@ impl_from!(
     Timestamp,
     Title,
     VerseBlock;
-    List,
-    Rule,
+    Inlinetask,
     TableRow
 );
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use nom::{
     bytes::complete::{tag, take_while},
     combinator::verify,
@@ -7,13 +9,52 @@ use nom::{
 
 // TODO: text-markup, entities, latex-fragments, subscript and superscript
 
-#[inline]
-pub fn parse_radio_target(input: &str) -> Option<(&str, &str)> {
-    parse_internal(input).ok()
+/// Radio Target Object
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct RadioTarget<'a> {
+    /// Radio target contents
+    pub target: Cow<'a, str>,
+}
+
+impl RadioTarget<'_> {
+    #[inline]
+    pub(crate) fn parse(input: &str) -> Option<(&str, RadioTarget<'_>)> {
+        parse_internal(input).ok()
+    }
+
+    pub fn into_owned(self) -> RadioTarget<'static> {
+        RadioTarget {
+            target: self.target.into_owned().into(),
+        }
+    }
+}
+
+/// Radio Link Object
+///
+/// Text matching a radio target anywhere in the document, ignoring case.
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct RadioLink<'a> {
+    /// Contents of the radio target this link points to
+    pub target: Cow<'a, str>,
+    /// Matched text
+    pub value: Cow<'a, str>,
+}
+
+impl RadioLink<'_> {
+    pub fn into_owned(self) -> RadioLink<'static> {
+        RadioLink {
+            target: self.target.into_owned().into(),
+            value: self.value.into_owned().into(),
+        }
+    }
 }
 
 #[inline]
-fn parse_internal(input: &str) -> IResult<&str, &str, ()> {
+fn parse_internal(input: &str) -> IResult<&str, RadioTarget<'_>, ()> {
     let (input, contents) = delimited(
         tag("<<<"),
         verify(
@@ -23,18 +64,96 @@ fn parse_internal(input: &str) -> IResult<&str, &str, ()> {
         tag(">>>"),
     )(input)?;
 
-    Ok((input, contents))
+    Ok((
+        input,
+        RadioTarget {
+            target: contents.into(),
+        },
+    ))
+}
+
+/// Finds the first occurrence of any of `targets` in `text`, returns its
+/// byte range and the index of the matched target.
+///
+/// Matching is case-insensitive, a whitespace in target matches any run of
+/// whitespaces, and the match must not be surrounded by alphanumeric
+/// characters.
+pub(crate) fn find_radio_link<S: AsRef<str>>(
+    text: &str,
+    targets: &[S],
+) -> Option<(usize, usize, usize)> {
+    let mut prev: Option<char> = None;
+
+    for (start, c) in text.char_indices() {
+        if !matches!(prev, Some(c) if c.is_alphanumeric()) {
+            let longest = targets
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, target)| {
+                    let len = match_target(&text[start..], target.as_ref())?;
+                    Some((len, idx))
+                })
+                .filter(|(len, _)| !text[start + len..].starts_with(char::is_alphanumeric))
+                .max_by_key(|(len, _)| *len);
+
+            if let Some((len, idx)) = longest {
+                return Some((start, start + len, idx));
+            }
+        }
+
+        prev = Some(c);
+    }
+
+    None
+}
+
+// length of text matching target at its beginning
+fn match_target(text: &str, target: &str) -> Option<usize> {
+    let mut pos = 0;
+
+    for (i, word) in target.split_whitespace().enumerate() {
+        if i > 0 {
+            let spaces = text[pos..].len() - text[pos..].trim_start().len();
+            if spaces == 0 {
+                return None;
+            }
+            pos += spaces;
+        }
+        for c in word.chars() {
+            let t = text[pos..].chars().next()?;
+            if c != t && !c.to_lowercase().eq(t.to_lowercase()) {
+                return None;
+            }
+            pos += t.len_utf8();
+        }
+    }
+
+    Some(pos).filter(|&pos| pos > 0)
 }
 
 #[test]
 fn parse() {
-    assert_eq!(parse_radio_target("<<<target>>>"), Some(("", "target")));
-    assert_eq!(parse_radio_target("<<<tar get>>>"), Some(("", "tar get")));
-
-    assert!(parse_radio_target("<<<target >>>").is_none());
-    assert!(parse_radio_target("<<< target>>>").is_none());
-    assert!(parse_radio_target("<<<ta<get>>>").is_none());
-    assert!(parse_radio_target("<<<ta>get>>>").is_none());
-    assert!(parse_radio_target("<<<ta\nget>>>").is_none());
-    assert!(parse_radio_target("<<<target>>").is_none());
+    let parse = |input| RadioTarget::parse(input).map(|(tail, radio)| (tail, radio.target));
+
+    assert_eq!(parse("<<<target>>>"), Some(("", "target".into())));
+    assert_eq!(parse("<<<tar get>>>"), Some(("", "tar get".into())));
+
+    assert!(parse("<<<target >>>").is_none());
+    assert!(parse("<<< target>>>").is_none());
+    assert!(parse("<<<ta<get>>>").is_none());
+    assert!(parse("<<<ta>get>>>").is_none());
+    assert!(parse("<<<ta\nget>>>").is_none());
+    assert!(parse("<<<target>>").is_none());
+}
+
+#[test]
+fn find() {
+    let targets = ["Org Mode", "org", "Émile"];
+
+    assert_eq!(find_radio_link("use org mode", &targets), Some((4, 12, 0)));
+    assert_eq!(find_radio_link("ORG\n  MODE!", &targets), Some((0, 10, 0)));
+    assert_eq!(find_radio_link("orgs, org.", &targets), Some((6, 9, 1)));
+    assert_eq!(find_radio_link("dear émile", &targets), Some((5, 11, 2)));
+    assert_eq!(find_radio_link("borg", &targets), None);
+    assert_eq!(find_radio_link("", &targets), None);
 }
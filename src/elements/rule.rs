@@ -1,39 +1,89 @@
 use nom::{bytes::complete::take_while_m_n, character::complete::space0, IResult};
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, eol};
 
 #[derive(Debug, Default, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-pub struct Rule {
+pub struct Rule<'a> {
     /// Numbers of blank lines between rule line and next non-blank line or
     /// buffer's end
     pub post_blank: usize,
+    /// Affiliated keywords attached to this rule
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub affiliated: Option<Box<AffiliatedKeywords<'a>>>,
 }
 
-impl Rule {
-    pub(crate) fn parse(input: &str) -> Option<(&str, Rule)> {
+impl Rule<'_> {
+    pub(crate) fn parse(input: &str) -> Option<(&str, Rule<'_>)> {
         parse_internal(input).ok()
     }
+
+    pub fn into_owned(self) -> Rule<'static> {
+        Rule {
+            post_blank: self.post_blank,
+            affiliated: self.affiliated.map(|a| Box::new(a.into_owned())),
+        }
+    }
 }
 
-fn parse_internal(input: &str) -> IResult<&str, Rule, ()> {
+fn parse_internal(input: &str) -> IResult<&str, Rule<'_>, ()> {
     let (input, _) = space0(input)?;
     let (input, _) = take_while_m_n(5, usize::max_value(), |c| c == '-')(input)?;
     let (input, _) = eol(input)?;
     let (input, post_blank) = blank_lines_count(input)?;
-    Ok((input, Rule { post_blank }))
+    Ok((
+        input,
+        Rule {
+            post_blank,
+            affiliated: None,
+        },
+    ))
 }
 
 #[test]
 fn parse() {
-    assert_eq!(Rule::parse("-----"), Some(("", Rule { post_blank: 0 })));
-    assert_eq!(Rule::parse("--------"), Some(("", Rule { post_blank: 0 })));
+    assert_eq!(
+        Rule::parse("-----"),
+        Some((
+            "",
+            Rule {
+                post_blank: 0,
+                affiliated: None
+            }
+        ))
+    );
+    assert_eq!(
+        Rule::parse("--------"),
+        Some((
+            "",
+            Rule {
+                post_blank: 0,
+                affiliated: None
+            }
+        ))
+    );
     assert_eq!(
         Rule::parse("-----\n\n\n"),
-        Some(("", Rule { post_blank: 2 }))
+        Some((
+            "",
+            Rule {
+                post_blank: 2,
+                affiliated: None
+            }
+        ))
+    );
+    assert_eq!(
+        Rule::parse("-----  \n"),
+        Some((
+            "",
+            Rule {
+                post_blank: 0,
+                affiliated: None
+            }
+        ))
     );
-    assert_eq!(Rule::parse("-----  \n"), Some(("", Rule { post_blank: 0 })));
 
     assert!(Rule::parse("").is_none());
     assert!(Rule::parse("----").is_none());
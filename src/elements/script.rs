@@ -0,0 +1,190 @@
+use crate::config::UseSubSuperscripts;
+use crate::elements::Element;
+
+/// Subscript or superscript object
+///
+/// # Syntax
+///
+/// ```text
+/// CHAR_SCRIPT
+/// CHAR^SCRIPT
+/// ```
+///
+/// `SCRIPT` can be `*`, an expression enclosed in braces or parentheses,
+/// or an optional sign followed by alphanumeric characters, commas,
+/// backslashes and dots, ending with an alphanumeric character.
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub(crate) struct Script<'a> {
+    superscript: bool,
+    brackets: bool,
+    contents: &'a str,
+}
+
+impl<'a> Script<'a> {
+    /// Parses a script starting at its `_` or `^` marker, the character
+    /// before the marker must be checked by the caller.
+    pub fn parse(text: &str, mode: UseSubSuperscripts) -> Option<(&str, Script<'_>)> {
+        let superscript = match text.as_bytes().first()? {
+            b'_' => false,
+            b'^' => true,
+            _ => return None,
+        };
+
+        let script = &text[1..];
+
+        let (contents, brackets, len) = match (mode, script.as_bytes().first()?) {
+            (UseSubSuperscripts::Never, _) => return None,
+            (_, b'{') => {
+                // contains at least one character
+                let len = balanced(script, b'{', b'}').filter(|&len| len > 2)?;
+                (&script[1..len - 1], true, len)
+            }
+            (UseSubSuperscripts::Braces, _) => return None,
+            (_, b'(') => {
+                let len = balanced(script, b'(', b')')?;
+                (&script[0..len], false, len)
+            }
+            (_, b'*') => (&script[0..1], false, 1),
+            _ => {
+                let bytes = script.as_bytes();
+                let start = if bytes[0] == b'+' || bytes[0] == b'-' {
+                    1
+                } else {
+                    0
+                };
+                let len = bytes[start..]
+                    .iter()
+                    .take_while(|&&c| {
+                        c.is_ascii_alphanumeric() || c == b'.' || c == b',' || c == b'\\'
+                    })
+                    .count();
+                // must end with an alphanumeric character
+                let len = bytes[start..start + len]
+                    .iter()
+                    .rposition(u8::is_ascii_alphanumeric)?
+                    + start
+                    + 1;
+                (&script[0..len], false, len)
+            }
+        };
+
+        Some((
+            &script[len..],
+            Script {
+                superscript,
+                brackets,
+                contents,
+            },
+        ))
+    }
+
+    pub fn into_element(self) -> (Element<'a>, &'a str) {
+        let Script {
+            superscript,
+            brackets,
+            contents,
+        } = self;
+        let element = if superscript {
+            Element::Superscript { brackets }
+        } else {
+            Element::Subscript { brackets }
+        };
+        (element, contents)
+    }
+}
+
+// length of a balanced expression in a single line, including its delimiters
+fn balanced(text: &str, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0;
+    for (i, &c) in text.as_bytes().iter().enumerate() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i + 1);
+            }
+        } else if c == b'\n' {
+            return None;
+        }
+    }
+    None
+}
+
+#[test]
+fn parse() {
+    use UseSubSuperscripts::*;
+
+    assert_eq!(
+        Script::parse("_2O", Always),
+        Some((
+            "",
+            Script {
+                superscript: false,
+                brackets: false,
+                contents: "2O"
+            }
+        ))
+    );
+    assert_eq!(
+        Script::parse("^{a {b}} c", Always),
+        Some((
+            " c",
+            Script {
+                superscript: true,
+                brackets: true,
+                contents: "a {b}"
+            }
+        ))
+    );
+    assert_eq!(
+        Script::parse("^(x+1)", Always),
+        Some((
+            "",
+            Script {
+                superscript: true,
+                brackets: false,
+                contents: "(x+1)"
+            }
+        ))
+    );
+    assert_eq!(
+        Script::parse("_-1.5, x", Always),
+        Some((
+            ", x",
+            Script {
+                superscript: false,
+                brackets: false,
+                contents: "-1.5"
+            }
+        ))
+    );
+    assert_eq!(
+        Script::parse("^*", Always),
+        Some((
+            "",
+            Script {
+                superscript: true,
+                brackets: false,
+                contents: "*"
+            }
+        ))
+    );
+    assert_eq!(Script::parse("_2", Braces), None);
+    assert_eq!(
+        Script::parse("_{2}", Braces),
+        Some((
+            "",
+            Script {
+                superscript: false,
+                brackets: true,
+                contents: "2"
+            }
+        ))
+    );
+    assert_eq!(Script::parse("_{2}", Never), None);
+    assert_eq!(Script::parse("_,", Always), None);
+    assert_eq!(Script::parse("^{a", Always), None);
+    assert_eq!(Script::parse("^{}", Always), None);
+}
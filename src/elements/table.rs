@@ -5,6 +5,7 @@ use nom::{
     Err, IResult,
 };
 
+use crate::elements::AffiliatedKeywords;
 use crate::parse::combinators::{blank_lines_count, line, lines_while};
 
 /// Table Element
@@ -16,12 +17,24 @@ pub enum Table<'a> {
     /// "org" type table
     #[cfg_attr(feature = "ser", serde(rename = "org"))]
     Org {
-        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        tblfm: Option<Cow<'a, str>>,
+        /// Formulas from `#+TBLFM:` lines following this table
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+        tblfm: Vec<Cow<'a, str>>,
         /// Numbers of blank lines between last table's line and next non-blank
         /// line or buffer's end
         post_blank: usize,
         has_header: bool,
+        /// Column formats from alignment cookie rows and column group rows,
+        /// which are not parsed as table rows
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+        columns: Vec<TableColumn>,
+        /// Alignment cookie rows and column group rows as written, with the
+        /// numbers of table rows before them
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Vec::is_empty"))]
+        special_rows: Vec<(usize, Cow<'a, str>)>,
+        /// Affiliated keywords attached to this table
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        affiliated: Option<Box<AffiliatedKeywords<'a>>>,
     },
     /// "table.el" type table
     #[cfg_attr(feature = "ser", serde(rename = "table.el"))]
@@ -30,6 +43,9 @@ pub enum Table<'a> {
         /// Numbers of blank lines between last table's line and next non-blank
         /// line or buffer's end
         post_blank: usize,
+        /// Affiliated keywords attached to this table
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        affiliated: Option<Box<AffiliatedKeywords<'a>>>,
     },
 }
 
@@ -67,29 +83,389 @@ impl Table<'_> {
             Table::TableEl {
                 value: content.into(),
                 post_blank,
+                affiliated: None,
             },
         ))
     }
 
+    /// Parses the grid of a "table.el" type table, returns its rows of
+    /// cells, or `None` if it's an "org" type table.
+    ///
+    /// Each cell is placed in the row where its top border is, and cells
+    /// spanning multiple rows or columns are returned only once.
+    ///
+    /// ```rust
+    /// use orgize::elements::Table;
+    ///
+    /// let (_, table) = Table::parse_table_el(
+    ///     "+---+---+\n| a | b |\n+===+===+\n| c     |\n+-------+\n",
+    /// )
+    /// .unwrap();
+    /// let rows = table.table_el_rows().unwrap();
+    ///
+    /// assert_eq!(rows[0][1].contents, "b");
+    /// assert!(rows[0][1].header);
+    /// assert_eq!(rows[1][0].colspan, 2);
+    /// ```
+    pub fn table_el_rows(&self) -> Option<Vec<Vec<TableElCell>>> {
+        match self {
+            Table::TableEl { value, .. } => Some(parse_table_el_grid(value)),
+            Table::Org { .. } => None,
+        }
+    }
+
     pub fn into_owned(self) -> Table<'static> {
         match self {
             Table::Org {
                 tblfm,
                 post_blank,
                 has_header,
+                columns,
+                special_rows,
+                affiliated,
             } => Table::Org {
-                tblfm: tblfm.map(Into::into).map(Cow::Owned),
+                tblfm: tblfm
+                    .into_iter()
+                    .map(|tblfm| tblfm.into_owned().into())
+                    .collect(),
                 post_blank,
                 has_header,
+                columns,
+                special_rows: special_rows
+                    .into_iter()
+                    .map(|(before, row)| (before, row.into_owned().into()))
+                    .collect(),
+                affiliated: affiliated.map(|a| Box::new(a.into_owned())),
             },
-            Table::TableEl { value, post_blank } => Table::TableEl {
+            Table::TableEl {
+                value,
+                post_blank,
+                affiliated,
+            } => Table::TableEl {
                 value: value.into_owned().into(),
                 post_blank,
+                affiliated: affiliated.map(|a| Box::new(a.into_owned())),
             },
         }
     }
 }
 
+/// Cell of a "table.el" type table
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct TableElCell {
+    /// Cell contents, non-empty lines are trimmed and joined with `\n`
+    pub contents: String,
+    /// Numbers of rows this cell spans
+    pub rowspan: usize,
+    /// Numbers of columns this cell spans
+    pub colspan: usize,
+    /// Whether this cell is above a `+===+` rule
+    pub header: bool,
+}
+
+// finds cells of a table.el grid, by tracing from top-left corners to
+// their right and bottom borders
+fn parse_table_el_grid(value: &str) -> Vec<Vec<TableElCell>> {
+    let lines: Vec<&str> = value
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let indent = lines
+        .iter()
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    // each line is split into display columns, a wide character is
+    // followed by an empty column and combining characters are kept with
+    // the preceding character
+    let grid: Vec<Vec<&str>> = lines
+        .iter()
+        .map(|line| {
+            let line = line[indent..].trim_end();
+            // byte range of each column
+            let mut columns: Vec<(usize, usize)> = vec![];
+            for (i, c) in line.char_indices() {
+                let end = i + c.len_utf8();
+                match (char_width(c), columns.iter().rposition(|(s, e)| s != e)) {
+                    (0, Some(last)) => columns[last].1 = end,
+                    (2, _) => columns.extend_from_slice(&[(i, end), (end, end)]),
+                    _ => columns.push((i, end)),
+                }
+            }
+            columns.into_iter().map(|(s, e)| &line[s..e]).collect()
+        })
+        .collect();
+
+    let at = |y: usize, x: usize| {
+        grid.get(y)
+            .and_then(|line| line.get(x))
+            .and_then(|s| s.chars().next())
+    };
+    let is_rule = |c: Option<char>| c == Some('-') || c == Some('=');
+    let is_border = |c: Option<char>| c == Some('|') || c == Some('+');
+
+    // rows and columns boundaries, and cells as (top, left, bottom, right)
+    let mut xs = vec![];
+    let mut ys = vec![];
+    let mut rects = vec![];
+
+    for (top, line) in grid.iter().enumerate() {
+        for (left, &c) in line.iter().enumerate() {
+            if c != "+" || !is_rule(at(top, left + 1)) || !is_border(at(top + 1, left)) {
+                continue;
+            }
+
+            let right =
+                (left + 1..line.len()).find(|&x| line[x] == "+" && is_border(at(top + 1, x)));
+            let bottom = (top + 1..grid.len())
+                .find(|&y| at(y, left) == Some('+') && is_rule(at(y, left + 1)));
+
+            if let (Some(right), Some(bottom)) = (right, bottom) {
+                let closed = (left..=right)
+                    .all(|x| is_rule(at(bottom, x)) || at(bottom, x) == Some('+'))
+                    && (top..=bottom).all(|y| is_border(at(y, right)));
+                if closed {
+                    xs.extend_from_slice(&[left, right]);
+                    ys.extend_from_slice(&[top, bottom]);
+                    rects.push((top, left, bottom, right));
+                }
+            }
+        }
+    }
+
+    xs.sort_unstable();
+    xs.dedup();
+    ys.sort_unstable();
+    ys.dedup();
+
+    // the first rule line like `+===+===+` ends the header
+    let header_end = (0..grid.len()).find(|&y| {
+        grid[y].first() == Some(&"+")
+            && grid[y].contains(&"=")
+            && grid[y].iter().all(|&c| c == "+" || c == "=")
+    });
+    let index = |boundaries: &[usize], v: usize| {
+        boundaries.iter().position(|&b| b == v).unwrap_or_default()
+    };
+
+    let mut rows = vec![vec![]; ys.len().saturating_sub(1)];
+
+    for (top, left, bottom, right) in rects {
+        let contents: Vec<String> = (top + 1..bottom)
+            .map(|y| {
+                let line = &grid[y][(left + 1).min(grid[y].len())..right.min(grid[y].len())];
+                line.concat().trim().to_string()
+            })
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let row = index(&ys, top);
+        rows[row].push((
+            index(&xs, left),
+            TableElCell {
+                contents: contents.join("\n"),
+                rowspan: index(&ys, bottom) - row,
+                colspan: index(&xs, right) - index(&xs, left),
+                header: header_end.is_some_and(|end| bottom <= end),
+            },
+        ));
+    }
+
+    rows.into_iter()
+        .map(|mut cells| {
+            cells.sort_by_key(|(column, _)| *column);
+            cells.into_iter().map(|(_, cell)| cell).collect()
+        })
+        .collect()
+}
+
+// display width of text in a monospace font
+pub(crate) fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+// east asian wide characters take two columns and combining characters
+// take none
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F => {
+            0
+        }
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ if c.is_control() => 0,
+        _ => 1,
+    }
+}
+
+/// Column format of an org table
+///
+/// ```text
+/// | / | <  |     > |
+/// |   | <l> | <r10> |
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+pub struct TableColumn {
+    /// Alignment from `<l>`, `<c>` or `<r>` cookie
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub align: Option<ColumnAlign>,
+    /// Width from `<N>` cookie
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub width: Option<usize>,
+    /// Column group boundary from `<`, `>` or `<>`
+    #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+    pub group: Option<ColumnGroup>,
+}
+
+/// Column alignment
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+pub enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Column group boundary
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+pub enum ColumnGroup {
+    /// `<`, starts a column group
+    Start,
+    /// `>`, ends a column group
+    End,
+    /// `<>`, a column group by itself
+    StartEnd,
+}
+
+impl TableColumn {
+    /// Returns the cookie of this column, e.g. `<r10>`, or an empty string.
+    pub fn cookie(&self) -> String {
+        if self.align.is_none() && self.width.is_none() {
+            return String::new();
+        }
+        let align = match self.align {
+            Some(ColumnAlign::Left) => "l",
+            Some(ColumnAlign::Center) => "c",
+            Some(ColumnAlign::Right) => "r",
+            None => "",
+        };
+        match self.width {
+            Some(width) => format!("<{}{}>", align, width),
+            None => format!("<{}>", align),
+        }
+    }
+
+    /// Returns the column group marker of this column, or an empty string.
+    pub fn group_marker(&self) -> &'static str {
+        match self.group {
+            Some(ColumnGroup::Start) => "<",
+            Some(ColumnGroup::End) => ">",
+            Some(ColumnGroup::StartEnd) => "<>",
+            None => "",
+        }
+    }
+}
+
+/// Parses a row consisting of alignment and width cookies, or a column
+/// group row starting with `/`, and merges it into `columns`. Returns
+/// `false` if it's a normal row.
+pub(crate) fn parse_special_row(line: &str, columns: &mut Vec<TableColumn>) -> bool {
+    let cells: Vec<_> = line.split_terminator('|').skip(1).map(str::trim).collect();
+
+    if cells.iter().all(|cell| cell.is_empty()) {
+        return false;
+    }
+
+    if cells[0] == "/" {
+        let groups: Option<Vec<_>> = cells[1..]
+            .iter()
+            .map(|cell| match *cell {
+                "" => Some(None),
+                "<" => Some(Some(ColumnGroup::Start)),
+                ">" => Some(Some(ColumnGroup::End)),
+                "<>" => Some(Some(ColumnGroup::StartEnd)),
+                _ => None,
+            })
+            .collect();
+        match groups {
+            Some(groups) => {
+                if columns.len() < cells.len() {
+                    columns.resize_with(cells.len(), Default::default);
+                }
+                for (column, group) in columns[1..].iter_mut().zip(groups) {
+                    column.group = group;
+                }
+                true
+            }
+            None => false,
+        }
+    } else {
+        let cookies: Option<Vec<_>> = cells
+            .iter()
+            .map(|cell| {
+                if cell.is_empty() {
+                    return Some(None);
+                }
+                let cookie = cell.strip_prefix('<')?.strip_suffix('>')?;
+                let (align, width) = match cookie.as_bytes().first() {
+                    Some(b'l') => (Some(ColumnAlign::Left), &cookie[1..]),
+                    Some(b'c') => (Some(ColumnAlign::Center), &cookie[1..]),
+                    Some(b'r') => (Some(ColumnAlign::Right), &cookie[1..]),
+                    _ => (None, cookie),
+                };
+                let width = if width.is_empty() {
+                    None
+                } else if width.bytes().all(|c| c.is_ascii_digit()) {
+                    Some(width.parse().ok()?)
+                } else {
+                    return None;
+                };
+                if align.is_none() && width.is_none() {
+                    return None;
+                }
+                Some(Some((align, width)))
+            })
+            .collect();
+        match cookies {
+            Some(cookies) => {
+                if columns.len() < cells.len() {
+                    columns.resize_with(cells.len(), Default::default);
+                }
+                for (column, cookie) in columns.iter_mut().zip(cookies) {
+                    if let Some((align, width)) = cookie {
+                        column.align = align;
+                        column.width = width;
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 /// Table Row Element
 ///
 /// # Syntax
@@ -142,6 +518,45 @@ pub enum TableCell {
     Body,
 }
 
+#[test]
+fn parse_special_rows() {
+    let mut columns = vec![];
+
+    assert!(parse_special_row("| <l> | | <r10> | <5> |", &mut columns));
+    assert!(parse_special_row("| / | < | > | <> |", &mut columns));
+    assert_eq!(
+        columns,
+        vec![
+            TableColumn {
+                align: Some(ColumnAlign::Left),
+                width: None,
+                group: None,
+            },
+            TableColumn {
+                align: None,
+                width: None,
+                group: Some(ColumnGroup::Start),
+            },
+            TableColumn {
+                align: Some(ColumnAlign::Right),
+                width: Some(10),
+                group: Some(ColumnGroup::End),
+            },
+            TableColumn {
+                align: None,
+                width: Some(5),
+                group: Some(ColumnGroup::StartEnd),
+            },
+        ]
+    );
+    assert_eq!(columns[2].cookie(), "<r10>");
+
+    assert!(!parse_special_row("| <> | a |", &mut columns));
+    assert!(!parse_special_row("| <l> | a |", &mut columns));
+    assert!(!parse_special_row("| / | a |", &mut columns));
+    assert!(!parse_special_row("| | |", &mut columns));
+}
+
 #[test]
 fn parse_table_el_() {
     assert_eq!(
@@ -160,10 +575,59 @@ fn parse_table_el_() {
   +---+
 "#
                 .into(),
-                post_blank: 1
+                post_blank: 1,
+                affiliated: None,
             }
         ))
     );
     assert!(Table::parse_table_el("").is_none());
     assert!(Table::parse_table_el("+----|---").is_none());
 }
+
+#[test]
+fn parse_table_el_grid_spans() {
+    let cell = |contents: &str, rowspan, colspan| TableElCell {
+        contents: contents.into(),
+        rowspan,
+        colspan,
+        header: false,
+    };
+
+    assert_eq!(
+        parse_table_el_grid(
+            r#"  +-----+-----+-----+
+  | a   | b         |
+  |     +-----+-----+
+  | aa  | c   | d   |
+  +-----+-----+     |
+  | e   | f   |     |
+  +-----+-----+-----+
+"#
+        ),
+        vec![
+            vec![cell("a\naa", 2, 1), cell("b", 1, 2)],
+            vec![cell("c", 1, 1), cell("d", 2, 1)],
+            vec![cell("e", 1, 1), cell("f", 1, 1)],
+        ]
+    );
+
+    // borders are aligned by display width
+    assert_eq!(
+        parse_table_el_grid("+------+---+\n| 梨   | b |\n+------+---+"),
+        vec![vec![cell("梨", 1, 1), cell("b", 1, 1)]]
+    );
+
+    // only a rule line of `+` and `=` ends the header
+    assert_eq!(
+        parse_table_el_grid("+-------+\n| a     |\n+-------+\n| x = y |\n+-------+"),
+        vec![vec![cell("a", 1, 1)], vec![cell("x = y", 1, 1)]]
+    );
+    let header = |contents: &str| TableElCell {
+        header: true,
+        ..cell(contents, 1, 1)
+    };
+    assert_eq!(
+        parse_table_el_grid("+---+\n| a |\n+===+\n| b |\n+---+"),
+        vec![vec![header("a")], vec![cell("b", 1, 1)]]
+    );
+}
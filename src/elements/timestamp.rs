@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::fmt;
 
 use nom::{
+    branch::alt,
     bytes::complete::{tag, take, take_till, take_while, take_while_m_n},
-    character::complete::{space0, space1},
+    character::complete::{digit1, space0, space1},
     combinator::{map, map_res, opt},
     sequence::preceded,
     IResult,
@@ -104,32 +106,52 @@ pub enum Timestamp<'a> {
     Active {
         start: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
     },
     Inactive {
         start: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
     },
     ActiveRange {
         start: Datetime<'a>,
         end: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
     },
     InactiveRange {
         start: Datetime<'a>,
         end: Datetime<'a>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        repeater: Option<Cow<'a, str>>,
+        repeater: Option<Repeater>,
         #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
-        delay: Option<Cow<'a, str>>,
+        delay: Option<Delay>,
+    },
+    /// Active timestamp with a time range inside a single day, e.g.
+    /// `<2024-03-01 Fri 10:00-11:30>`, `end` has the same date as `start`
+    ActiveTimeRange {
+        start: Datetime<'a>,
+        end: Datetime<'a>,
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        repeater: Option<Repeater>,
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        delay: Option<Delay>,
+    },
+    /// Inactive timestamp with a time range inside a single day, e.g.
+    /// `[2024-03-01 Fri 10:00-11:30]`, `end` has the same date as `start`
+    InactiveTimeRange {
+        start: Datetime<'a>,
+        end: Datetime<'a>,
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        repeater: Option<Repeater>,
+        #[cfg_attr(feature = "ser", serde(skip_serializing_if = "Option::is_none"))]
+        delay: Option<Delay>,
     },
     Diary {
         value: Cow<'a, str>,
@@ -157,8 +179,8 @@ impl Timestamp<'_> {
                 delay,
             } => Timestamp::Active {
                 start: start.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
             },
             Timestamp::Inactive {
                 start,
@@ -166,8 +188,8 @@ impl Timestamp<'_> {
                 delay,
             } => Timestamp::Inactive {
                 start: start.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
             },
             Timestamp::ActiveRange {
                 start,
@@ -177,8 +199,8 @@ impl Timestamp<'_> {
             } => Timestamp::ActiveRange {
                 start: start.into_owned(),
                 end: end.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
             },
             Timestamp::InactiveRange {
                 start,
@@ -188,8 +210,30 @@ impl Timestamp<'_> {
             } => Timestamp::InactiveRange {
                 start: start.into_owned(),
                 end: end.into_owned(),
-                repeater: repeater.map(Into::into).map(Cow::Owned),
-                delay: delay.map(Into::into).map(Cow::Owned),
+                repeater,
+                delay,
+            },
+            Timestamp::ActiveTimeRange {
+                start,
+                end,
+                repeater,
+                delay,
+            } => Timestamp::ActiveTimeRange {
+                start: start.into_owned(),
+                end: end.into_owned(),
+                repeater,
+                delay,
+            },
+            Timestamp::InactiveTimeRange {
+                start,
+                end,
+                repeater,
+                delay,
+            } => Timestamp::InactiveTimeRange {
+                start: start.into_owned(),
+                end: end.into_owned(),
+                repeater,
+                delay,
             },
             Timestamp::Diary { value } => Timestamp::Diary {
                 value: value.into_owned().into(),
@@ -204,39 +248,40 @@ pub fn parse_active(input: &str) -> IResult<&str, Timestamp, ()> {
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
+        let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag(">")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
         end.minute = Some(minute);
         return Ok((
             input,
-            Timestamp::ActiveRange {
+            Timestamp::ActiveTimeRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ));
     }
 
+    let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
     let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
     let (input, _) = tag(">")(input)?;
 
     if input.starts_with("--<") {
         let (input, end) = parse_datetime(&input["--<".len()..])?;
+        // repeater and delay of the end are ignored
+        let (input, _) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag(">")(input)?;
         Ok((
             input,
             Timestamp::ActiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ))
     } else {
@@ -244,8 +289,8 @@ pub fn parse_active(input: &str) -> IResult<&str, Timestamp, ()> {
             input,
             Timestamp::Active {
                 start,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ))
     }
@@ -257,39 +302,40 @@ pub fn parse_inactive(input: &str) -> IResult<&str, Timestamp, ()> {
 
     if input.starts_with('-') {
         let (input, (hour, minute)) = parse_time(&input[1..])?;
+        let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag("]")(input)?;
         let mut end = start.clone();
         end.hour = Some(hour);
         end.minute = Some(minute);
         return Ok((
             input,
-            Timestamp::InactiveRange {
+            Timestamp::InactiveTimeRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ));
     }
 
+    let (input, (repeater, delay)) = parse_repeater_or_delay(input)?;
     let (input, _) = space0(input)?;
-    // TODO: delay-or-repeater
     let (input, _) = tag("]")(input)?;
 
     if input.starts_with("--[") {
         let (input, end) = parse_datetime(&input["--[".len()..])?;
+        // repeater and delay of the end are ignored
+        let (input, _) = parse_repeater_or_delay(input)?;
         let (input, _) = space0(input)?;
-        // TODO: delay-or-repeater
         let (input, _) = tag("]")(input)?;
         Ok((
             input,
             Timestamp::InactiveRange {
                 start,
                 end,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ))
     } else {
@@ -297,8 +343,8 @@ pub fn parse_inactive(input: &str) -> IResult<&str, Timestamp, ()> {
             input,
             Timestamp::Inactive {
                 start,
-                repeater: None,
-                delay: None,
+                repeater,
+                delay,
             },
         ))
     }
@@ -360,52 +406,198 @@ fn parse_datetime(input: &str) -> IResult<&str, Datetime, ()> {
     ))
 }
 
-// TODO
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum RepeaterType {
-//     Cumulate,
-//     CatchUp,
-//     Restart,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum DelayType {
-//     All,
-//     First,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub enum TimeUnit {
-//     Hour,
-//     Day,
-//     Week,
-//     Month,
-//     Year,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub struct Repeater {
-//     pub ty: RepeaterType,
-//     pub value: usize,
-//     pub unit: TimeUnit,
-// }
-
-// #[cfg_attr(test, derive(PartialEq))]
-// #[cfg_attr(feature = "ser", derive(serde::Serialize))]
-// #[derive(Debug, Copy, Clone)]
-// pub struct Delay {
-//     pub ty: DelayType,
-//     pub value: usize,
-//     pub unit: TimeUnit,
-// }
+/// Repeater type
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Copy, Clone)]
+pub enum RepeaterType {
+    /// `+`, shifts the date by the interval once
+    Cumulate,
+    /// `++`, shifts the date by the interval until it's in the future
+    CatchUp,
+    /// `.+`, shifts the date from today
+    Restart,
+}
+
+/// Delay type
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Copy, Clone)]
+pub enum DelayType {
+    /// `-`, applies to all repetitions
+    All,
+    /// `--`, applies to the first repetition only
+    First,
+}
+
+/// Time unit of repeaters and delays
+#[cfg_attr(test, derive(PartialEq))]
+#[cfg_attr(feature = "ser", derive(serde::Serialize))]
+#[cfg_attr(feature = "ser", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Copy, Clone)]
+pub enum TimeUnit {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Timestamp repeater
+///
+/// Serialized as its original syntax, e.g. `".+1d/3d"`.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Copy, Clone)]
+pub struct Repeater {
+    pub ty: RepeaterType,
+    pub value: usize,
+    pub unit: TimeUnit,
+    /// Maximum interval of a habit, e.g. `3d` in `.+1d/3d`
+    pub habit: Option<(usize, TimeUnit)>,
+}
+
+/// Timestamp warning delay
+///
+/// Serialized as its original syntax, e.g. `"--2d"`.
+#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Copy, Clone)]
+pub struct Delay {
+    pub ty: DelayType,
+    pub value: usize,
+    pub unit: TimeUnit,
+    /// Whether this delay is written before the repeater, e.g. `-1w` in
+    /// `<2024-01-01 Mon -1w ++2m>`
+    pub before_repeater: bool,
+}
+
+impl TimeUnit {
+    pub fn as_char(self) -> char {
+        match self {
+            TimeUnit::Hour => 'h',
+            TimeUnit::Day => 'd',
+            TimeUnit::Week => 'w',
+            TimeUnit::Month => 'm',
+            TimeUnit::Year => 'y',
+        }
+    }
+}
+
+impl fmt::Display for Repeater {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mark = match self.ty {
+            RepeaterType::Cumulate => "+",
+            RepeaterType::CatchUp => "++",
+            RepeaterType::Restart => ".+",
+        };
+        write!(f, "{}{}{}", mark, self.value, self.unit.as_char())?;
+        if let Some((value, unit)) = self.habit {
+            write!(f, "/{}{}", value, unit.as_char())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mark = match self.ty {
+            DelayType::All => "-",
+            DelayType::First => "--",
+        };
+        write!(f, "{}{}{}", mark, self.value, self.unit.as_char())
+    }
+}
+
+#[cfg(feature = "ser")]
+impl serde::Serialize for Repeater {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "ser")]
+impl serde::Serialize for Delay {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// optional repeater and delay, in any order
+fn parse_repeater_or_delay(
+    mut input: &str,
+) -> IResult<&str, (Option<Repeater>, Option<Delay>), ()> {
+    let mut repeater = None;
+    let mut delay: Option<Delay> = None;
+
+    while let Ok((tail, _)) = space1::<_, ()>(input) {
+        if let (None, Ok((tail, r))) = (repeater, parse_repeater(tail)) {
+            if let Some(delay) = &mut delay {
+                delay.before_repeater = true;
+            }
+            repeater = Some(r);
+            input = tail;
+        } else if let (None, Ok((tail, d))) = (delay, parse_delay(tail)) {
+            delay = Some(d);
+            input = tail;
+        } else {
+            break;
+        }
+    }
+
+    Ok((input, (repeater, delay)))
+}
+
+fn parse_repeater(input: &str) -> IResult<&str, Repeater, ()> {
+    let (input, ty) = alt((
+        map(tag("++"), |_| RepeaterType::CatchUp),
+        map(tag(".+"), |_| RepeaterType::Restart),
+        map(tag("+"), |_| RepeaterType::Cumulate),
+    ))(input)?;
+    let (input, (value, unit)) = parse_interval(input)?;
+    let (input, habit) = opt(preceded(tag("/"), parse_interval))(input)?;
+
+    Ok((
+        input,
+        Repeater {
+            ty,
+            value,
+            unit,
+            habit,
+        },
+    ))
+}
+
+fn parse_delay(input: &str) -> IResult<&str, Delay, ()> {
+    let (input, ty) = alt((
+        map(tag("--"), |_| DelayType::First),
+        map(tag("-"), |_| DelayType::All),
+    ))(input)?;
+    let (input, (value, unit)) = parse_interval(input)?;
+
+    Ok((
+        input,
+        Delay {
+            ty,
+            value,
+            unit,
+            before_repeater: false,
+        },
+    ))
+}
+
+fn parse_interval(input: &str) -> IResult<&str, (usize, TimeUnit), ()> {
+    let (input, value) = map_res(digit1, str::parse)(input)?;
+    let (input, unit) = alt((
+        map(tag("h"), |_| TimeUnit::Hour),
+        map(tag("d"), |_| TimeUnit::Day),
+        map(tag("w"), |_| TimeUnit::Week),
+        map(tag("m"), |_| TimeUnit::Month),
+        map(tag("y"), |_| TimeUnit::Year),
+    ))(input)?;
+
+    Ok((input, (value, unit)))
+}
 
 #[test]
 fn parse() {
@@ -457,7 +649,7 @@ fn parse() {
         parse_active("<2003-09-16 Tue 09:39-10:39>"),
         Ok((
             "",
-            Timestamp::ActiveRange {
+            Timestamp::ActiveTimeRange {
                 start: Datetime {
                     year: 2003,
                     month: 9,
@@ -479,4 +671,57 @@ fn parse() {
             },
         ))
     );
+    assert_eq!(
+        parse_active("<2019-10-28 Mon 08:00 .+1d/3d --2h>"),
+        Ok((
+            "",
+            Timestamp::Active {
+                start: Datetime {
+                    year: 2019,
+                    month: 10,
+                    day: 28,
+                    dayname: "Mon".into(),
+                    hour: Some(8),
+                    minute: Some(0),
+                },
+                repeater: Some(Repeater {
+                    ty: RepeaterType::Restart,
+                    value: 1,
+                    unit: TimeUnit::Day,
+                    habit: Some((3, TimeUnit::Day)),
+                }),
+                delay: Some(Delay {
+                    ty: DelayType::First,
+                    value: 2,
+                    unit: TimeUnit::Hour,
+                    before_repeater: false,
+                }),
+            },
+        ))
+    );
+    assert_eq!(
+        parse_inactive("[2019-10-28 Mon -1w ++2m]").map(|(_, t)| match t {
+            Timestamp::Inactive {
+                repeater, delay, ..
+            } => (
+                repeater.unwrap().to_string(),
+                delay.unwrap().to_string(),
+                delay.unwrap().before_repeater,
+            ),
+            _ => unreachable!(),
+        }),
+        Ok(("++2m".into(), "-1w".into(), true))
+    );
+    assert!(parse_active("<2019-10-28 Mon +1x>").is_err());
+    assert!(parse_active("<2019-10-28 Mon +1d +1w>").is_err());
+    assert_eq!(
+        parse_inactive("[2024-03-01 Fri 10:00-11:30 -1d]").map(|(_, t)| match t {
+            Timestamp::InactiveTimeRange { start, end, .. } => (
+                (start.day, start.hour, start.minute),
+                (end.day, end.hour, end.minute),
+            ),
+            _ => unreachable!(),
+        }),
+        Ok(((1, Some(10), Some(0)), (1, Some(11), Some(30))))
+    );
 }
@@ -6,7 +6,7 @@ use std::{borrow::Cow, iter::FromIterator};
 use memchr::memrchr2;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while},
+    bytes::complete::{tag, take_while},
     character::complete::{anychar, line_ending, space1},
     combinator::{map, opt, verify},
     error::{make_error, ErrorKind},
@@ -124,6 +124,10 @@ impl Default for Title<'_> {
 }
 
 /// Properties
+///
+/// Pairs are stored as they appear in the property drawer, so keys of
+/// `:KEY+:` lines keep their trailing plus sign. Use `get` to look up a
+/// value with Org's accumulation semantics.
 #[derive(Default, Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 #[cfg_attr(feature = "ser", derive(serde::Serialize))]
@@ -140,6 +144,49 @@ impl<'a> PropertiesMap<'a> {
         self.pairs.is_empty()
     }
 
+    /// Returns the value of property `key`, ignoring case.
+    ///
+    /// Values of `KEY+` properties are appended to the previous value,
+    /// separated by a space. Properties without value are returned as an
+    /// empty string.
+    ///
+    /// ```rust
+    /// use orgize::elements::PropertiesMap;
+    ///
+    /// let map: PropertiesMap = vec![
+    ///     ("var".into(), "foo=1".into()),
+    ///     ("VAR+".into(), "bar=2".into()),
+    ///     ("empty".into(), "".into()),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(map.get("Var").as_deref(), Some("foo=1 bar=2"));
+    /// assert_eq!(map.get("empty").as_deref(), Some(""));
+    /// assert_eq!(map.get("missing"), None);
+    /// ```
+    pub fn get(&self, key: &str) -> Option<Cow<'a, str>> {
+        let mut result: Option<Cow<'a, str>> = None;
+
+        for (name, value) in &self.pairs {
+            if name.eq_ignore_ascii_case(key) {
+                result = Some(value.clone());
+            } else if name
+                .strip_suffix('+')
+                .is_some_and(|name| name.eq_ignore_ascii_case(key))
+            {
+                result = match result {
+                    Some(prev) if prev.is_empty() => Some(value.clone()),
+                    Some(prev) if value.is_empty() => Some(prev),
+                    Some(prev) => Some(format!("{} {}", prev, value).into()),
+                    None => Some(value.clone()),
+                };
+            }
+        }
+
+        result
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &(Cow<'a, str>, Cow<'a, str>)> {
         self.pairs.iter()
     }
@@ -257,7 +304,7 @@ fn is_tag_line(input: &str) -> bool {
 }
 
 #[inline]
-fn parse_properties_drawer(input: &str) -> IResult<&str, PropertiesMap<'_>, ()> {
+pub(crate) fn parse_properties_drawer(input: &str) -> IResult<&str, PropertiesMap<'_>, ()> {
     let (input, (drawer, content)) = parse_drawer_without_blank(input.trim_start())?;
     if drawer.name != "PROPERTIES" {
         return Err(Err::Error(make_error(input, ErrorKind::Tag)));
@@ -277,11 +324,17 @@ fn parse_properties_drawer(input: &str) -> IResult<&str, PropertiesMap<'_>, ()>
 fn parse_node_property(input: &str) -> IResult<&str, (&str, &str), ()> {
     let (input, _) = blank_lines_count(input)?;
     let input = input.trim_start();
-    let (input, name) = map(delimited(tag(":"), take_until(":"), tag(":")), |s: &str| {
-        s.trim_end_matches('+')
-    })(input)?;
-    let (input, value) = line(input)?;
-    Ok((input, (name, value.trim())))
+    let (input, _) = tag(":")(input)?;
+    let (input, content) = line(input)?;
+    // names may contain colons, e.g. `:header-args:python:`, so the name
+    // ends at the first colon followed by a whitespace or the end of line
+    let end = content
+        .match_indices(':')
+        .map(|(i, _)| i)
+        .find(|&i| content[i + 1..].is_empty() || content[i + 1..].starts_with(char::is_whitespace))
+        .filter(|&i| i > 0 && !content[0..i].contains(char::is_whitespace))
+        .ok_or(Err::Error(()))?;
+    Ok((input, (&content[0..end], content[end + 1..].trim())))
 }
 
 #[test]
@@ -506,7 +559,29 @@ fn parse_properties_drawer_() {
                 .into_iter()
                 .collect::<PropertiesMap>()
         ))
-    )
+    );
+    assert_eq!(
+        parse_properties_drawer(":PROPERTIES:\n:VAR: a=1\n:var+: b=2\n:EMPTY:\n:END:"),
+        Ok((
+            "",
+            vec![
+                ("VAR".into(), "a=1".into()),
+                ("var+".into(), "b=2".into()),
+                ("EMPTY".into(), "".into())
+            ]
+            .into_iter()
+            .collect::<PropertiesMap>()
+        ))
+    );
+    assert_eq!(
+        parse_properties_drawer(":PROPERTIES:\n:header-args:python: :session py\n:END:"),
+        Ok((
+            "",
+            vec![("header-args:python".into(), ":session py".into())]
+                .into_iter()
+                .collect::<PropertiesMap>()
+        ))
+    );
 }
 
 #[test]
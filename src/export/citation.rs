@@ -0,0 +1,453 @@
+use std::fs;
+use std::io::{Error, Write};
+use std::path::Path;
+
+use crate::bibtex::{self, BibEntry, BibName};
+use crate::elements::Citation;
+use crate::export::HtmlEscape;
+use crate::Org;
+
+/// Citation Processor
+///
+/// Renders citations and the bibliography in html export. Citations are
+/// passed in document order, and the bibliography is written at the
+/// `#+PRINT_BIBLIOGRAPHY:` keyword.
+pub trait CitationProcessor {
+    /// Writes a citation.
+    fn write_citation(&mut self, w: &mut dyn Write, citation: &Citation) -> Result<(), Error>;
+
+    /// Writes the bibliography of references cited so far.
+    fn write_bibliography(&mut self, w: &mut dyn Write) -> Result<(), Error>;
+}
+
+/// Default Citation Processor
+///
+/// Writes citations like `(see doe2020, p. 3; smith1999)`, with each key
+/// linked to its entry in the bibliography, which simply lists cited keys.
+///
+/// Citations with `t` or `text` style are written without parentheses, and
+/// `nocite` citations only add their keys to the bibliography.
+#[derive(Default, Debug)]
+pub struct DefaultCitationProcessor {
+    // cited keys in order of their first appearance
+    keys: Vec<String>,
+}
+
+impl DefaultCitationProcessor {
+    /// Returns the cited keys in order of their first appearance.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+}
+
+impl CitationProcessor for DefaultCitationProcessor {
+    fn write_citation(&mut self, w: &mut dyn Write, citation: &Citation) -> Result<(), Error> {
+        for reference in &citation.references {
+            if !self.keys.iter().any(|key| *key == reference.key) {
+                self.keys.push(reference.key.to_string());
+            }
+        }
+
+        write_citation_with(w, citation, ("(", ")"), |w, key| {
+            write!(
+                w,
+                "<a href=\"#cite-{}\">{}</a>",
+                HtmlEscape(key),
+                HtmlEscape(key)
+            )
+        })
+    }
+
+    fn write_bibliography(&mut self, w: &mut dyn Write) -> Result<(), Error> {
+        if self.keys.is_empty() {
+            return Ok(());
+        }
+
+        write!(w, "<div class=\"bibliography\"><ul>")?;
+        for key in &self.keys {
+            write!(
+                w,
+                "<li id=\"cite-{}\">{}</li>",
+                HtmlEscape(key),
+                HtmlEscape(key)
+            )?;
+        }
+        write!(w, "</ul></div>")
+    }
+}
+
+/// Reference style of [`BibtexCitationProcessor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// Citations like `(Doe 2020, p. 3)`, bibliography sorted by authors
+    /// and year
+    AuthorYear,
+    /// Citations like `[1, p. 3]`, bibliography numbered in order of first
+    /// citation
+    Numeric,
+}
+
+impl CitationStyle {
+    /// Formats an entry as a plain text reference.
+    ///
+    /// ```rust
+    /// use orgize::bibtex::parse;
+    /// use orgize::export::CitationStyle;
+    ///
+    /// let entries = parse("@book{doe, author = {Doe, John and Ann Lee}, \
+    ///                      title = {A Book}, publisher = {Acme}, year = 2020}");
+    ///
+    /// assert_eq!(
+    ///     CitationStyle::AuthorYear.format_entry(&entries[0]),
+    ///     "Doe, J. & Lee, A. (2020). A Book. Acme."
+    /// );
+    /// assert_eq!(
+    ///     CitationStyle::Numeric.format_entry(&entries[0]),
+    ///     "J. Doe and A. Lee, A Book, Acme, 2020."
+    /// );
+    /// ```
+    pub fn format_entry(self, entry: &BibEntry) -> String {
+        let authors = entry.authors();
+        let title = entry.field("title");
+        let source = source(entry);
+
+        match self {
+            CitationStyle::AuthorYear => {
+                let mut output = String::new();
+                if !authors.is_empty() {
+                    let names: Vec<_> = authors
+                        .iter()
+                        .map(|name| join_nonempty(&[&name.last, &name.initials()], ", "))
+                        .collect();
+                    output += &join_names(&names, ", ", " & ");
+                    output += " ";
+                }
+                output += &format!("({}).", entry.year().unwrap_or("n.d."));
+                for part in title.into_iter().chain(source.as_deref()) {
+                    output += " ";
+                    output += part.trim_end_matches('.');
+                    output += ".";
+                }
+                output
+            }
+            CitationStyle::Numeric => {
+                let names: Vec<_> = authors
+                    .iter()
+                    .map(|name| join_nonempty(&[&name.initials(), &name.last], " "))
+                    .collect();
+                let names = join_names(&names, ", ", " and ");
+                let parts: Vec<_> = Some(names.as_str())
+                    .filter(|names| !names.is_empty())
+                    .into_iter()
+                    .chain(title)
+                    .chain(source.as_deref())
+                    .chain(entry.year())
+                    .collect();
+                format!("{}.", parts.join(", ").trim_end_matches('.'))
+            }
+        }
+    }
+}
+
+/// BibTeX Citation Processor
+///
+/// Resolves citation keys against BibTeX entries, writes citations in the
+/// given [`CitationStyle`], and the bibliography as an ordered list of
+/// formatted references. Each citation is linked to its reference, keys
+/// without an entry are written as-is.
+///
+/// ```rust
+/// use orgize::bibtex::parse;
+/// use orgize::export::{BibtexCitationProcessor, CitationStyle, DefaultHtmlHandler};
+/// use orgize::Org;
+///
+/// let entries = parse("@article{doe2020, author = {John Doe}, title = {Title}, \
+///                      journal = {Journal}, year = 2020}");
+///
+/// let mut handler = DefaultHtmlHandler::default();
+/// handler.citation_processor =
+///     Box::new(BibtexCitationProcessor::new(entries, CitationStyle::Numeric));
+///
+/// let mut writer = Vec::new();
+/// Org::parse("See [cite:@doe2020].\n#+PRINT_BIBLIOGRAPHY:")
+///     .write_html_custom(&mut writer, &mut handler)
+///     .unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(writer).unwrap(),
+///     "<main><section><p>See <span class=\"citation\">[<a href=\"#cite-doe2020\">1</a>]</span>.\
+///      </p><div class=\"bibliography\"><ol><li id=\"cite-doe2020\">J. Doe, Title, Journal, 2020.\
+///      </li></ol></div></section></main>"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct BibtexCitationProcessor {
+    entries: Vec<BibEntry>,
+    style: CitationStyle,
+    // indices of cited entries in order of their first citation
+    cited: Vec<usize>,
+}
+
+impl BibtexCitationProcessor {
+    /// Creates a processor from parsed BibTeX entries.
+    pub fn new(entries: Vec<BibEntry>, style: CitationStyle) -> Self {
+        BibtexCitationProcessor {
+            entries,
+            style,
+            cited: Vec::new(),
+        }
+    }
+
+    /// Creates a processor from BibTeX files listed in `#+BIBLIOGRAPHY:`
+    /// keywords of the document, relative paths are resolved against `dir`.
+    pub fn from_org<P: AsRef<Path>>(
+        org: &Org,
+        dir: P,
+        style: CitationStyle,
+    ) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        for keyword in org.keywords() {
+            if keyword.key.eq_ignore_ascii_case("BIBLIOGRAPHY") {
+                let path = dir.as_ref().join(keyword.value.trim());
+                entries.extend(bibtex::parse(&fs::read_to_string(path)?));
+            }
+        }
+        Ok(BibtexCitationProcessor::new(entries, style))
+    }
+
+    /// Returns the entry of the given key.
+    pub fn entry(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Returns cited entries in bibliography order.
+    pub fn cited(&self) -> Vec<&BibEntry> {
+        let mut cited: Vec<_> = self.cited.iter().map(|&i| &self.entries[i]).collect();
+        if self.style == CitationStyle::AuthorYear {
+            cited.sort_by_cached_key(|entry| {
+                let authors: Vec<_> = entry.authors().iter().map(|name| &name.last).collect();
+                (
+                    authors,
+                    entry.year().map(str::to_string),
+                    entry.field("title").map(str::to_string),
+                )
+            });
+        }
+        cited
+    }
+}
+
+impl CitationProcessor for BibtexCitationProcessor {
+    fn write_citation(&mut self, w: &mut dyn Write, citation: &Citation) -> Result<(), Error> {
+        for reference in &citation.references {
+            let index = self
+                .entries
+                .iter()
+                .position(|entry| entry.key == reference.key);
+            if let Some(index) = index.filter(|index| !self.cited.contains(index)) {
+                self.cited.push(index);
+            }
+        }
+
+        let textual = is_textual(citation);
+        let delimiters = match self.style {
+            CitationStyle::AuthorYear => ("(", ")"),
+            CitationStyle::Numeric => ("[", "]"),
+        };
+
+        write_citation_with(w, citation, delimiters, |w, key| {
+            let entry = match self.entries.iter().find(|entry| entry.key == key) {
+                Some(entry) => entry,
+                None => return write!(w, "{}", HtmlEscape(key)),
+            };
+            let authors = short_authors(entry.authors());
+            let label = match self.style {
+                CitationStyle::AuthorYear => {
+                    let year = entry.year().unwrap_or("n.d.");
+                    if textual {
+                        format!("{} ({})", authors, year)
+                    } else {
+                        join_nonempty(&[&authors, year], " ")
+                    }
+                }
+                CitationStyle::Numeric => {
+                    let number = self.cited.iter().position(|&i| self.entries[i].key == key);
+                    let number = number.map(|n| n + 1).unwrap_or_default();
+                    if textual {
+                        format!("{} [{}]", authors, number)
+                    } else {
+                        number.to_string()
+                    }
+                }
+            };
+            write!(
+                w,
+                "<a href=\"#cite-{}\">{}</a>",
+                HtmlEscape(key),
+                HtmlEscape(label.trim())
+            )
+        })
+    }
+
+    fn write_bibliography(&mut self, w: &mut dyn Write) -> Result<(), Error> {
+        if self.cited.is_empty() {
+            return Ok(());
+        }
+
+        write!(w, "<div class=\"bibliography\"><ol>")?;
+        for entry in self.cited() {
+            write!(
+                w,
+                "<li id=\"cite-{}\">{}</li>",
+                HtmlEscape(&entry.key),
+                HtmlEscape(self.style.format_entry(entry))
+            )?;
+        }
+        write!(w, "</ol></div>")
+    }
+}
+
+// container of an entry, e.g. `Journal, 12(3), 45-67`
+fn source(entry: &BibEntry) -> Option<String> {
+    let container = entry
+        .field("journal")
+        .or_else(|| entry.field("journaltitle"))
+        .map(str::to_string)
+        .or_else(|| {
+            entry
+                .field("booktitle")
+                .map(|title| format!("In {}", title))
+        })
+        .or_else(|| entry.field("publisher").map(str::to_string))?;
+    let volume = match (entry.field("volume"), entry.field("number")) {
+        (Some(volume), Some(number)) => format!("{}({})", volume, number),
+        (Some(volume), None) => volume.to_string(),
+        (None, Some(number)) => format!("({})", number),
+        (None, None) => String::new(),
+    };
+    let pages = entry.field("pages").unwrap_or_default().replace("--", "-");
+    Some(join_nonempty(&[&container, &volume, &pages], ", "))
+}
+
+// `Doe`, `Doe and Lee` or `Doe et al.`
+fn short_authors(authors: &[BibName]) -> String {
+    match authors {
+        [] => String::new(),
+        [a] => a.last.clone(),
+        [a, b] => format!("{} and {}", a.last, b.last),
+        [a, ..] => format!("{} et al.", a.last),
+    }
+}
+
+// `a`, `a and b` or `a, b and c`
+fn join_names(names: &[String], sep: &str, last_sep: &str) -> String {
+    match names.split_last() {
+        Some((last, init)) if !init.is_empty() => format!("{}{}{}", init.join(sep), last_sep, last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+fn join_nonempty(parts: &[&str], sep: &str) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+// `t` and `text` styles are written without parentheses
+fn is_textual(citation: &Citation) -> bool {
+    let style = citation.style.as_deref().unwrap_or_default();
+    let style = style.split('/').next().unwrap_or_default();
+    style == "t" || style == "text"
+}
+
+/// Writes a citation with its prefixes and suffixes enclosed in
+/// `delimiters`, using `write_key` for each reference key.
+pub(crate) fn write_citation_with<F>(
+    w: &mut dyn Write,
+    citation: &Citation,
+    (open, close): (&str, &str),
+    mut write_key: F,
+) -> Result<(), Error>
+where
+    F: FnMut(&mut dyn Write, &str) -> Result<(), Error>,
+{
+    let style = citation.style.as_deref().unwrap_or_default();
+    let style = style.split('/').next().unwrap_or_default();
+
+    if style == "nocite" {
+        return Ok(());
+    }
+
+    let parens = !is_textual(citation);
+
+    write!(w, "<span class=\"citation\">")?;
+    if parens {
+        write!(w, "{}", open)?;
+    }
+    if let Some(prefix) = &citation.prefix {
+        write!(w, "{} ", HtmlEscape(prefix.trim()))?;
+    }
+    for (i, reference) in citation.references.iter().enumerate() {
+        if i > 0 {
+            write!(w, "; ")?;
+        }
+        if let Some(prefix) = &reference.prefix {
+            write!(w, "{}", HtmlEscape(prefix.trim_start()))?;
+        }
+        write_key(w, &reference.key)?;
+        if let Some(suffix) = &reference.suffix {
+            write!(w, "{}", HtmlEscape(suffix.trim_end()))?;
+        }
+    }
+    if let Some(suffix) = &citation.suffix {
+        write!(w, "; {}", HtmlEscape(suffix.trim()))?;
+    }
+    if parens {
+        write!(w, "{}", close)?;
+    }
+    write!(w, "</span>")
+}
+
+#[test]
+fn bibtex_citations() {
+    use crate::export::DefaultHtmlHandler;
+
+    let entries = bibtex::parse(
+        "@book{lee, author = {Lee, Ann}, title = {Later}, publisher = {Acme}, year = 2021}
+         @article{doe, author = {John Doe and Ann Lee and Bob Roe}, title = {Earlier.},
+                  journal = {Journal}, volume = 12, number = 3, pages = {45--67}, year = 2020}",
+    );
+
+    let mut handler = DefaultHtmlHandler::default();
+    handler.citation_processor = Box::new(BibtexCitationProcessor::new(
+        entries,
+        CitationStyle::AuthorYear,
+    ));
+
+    let mut writer = Vec::new();
+    Org::parse(
+        "[cite/t:@lee] and [cite:see @doe p. 3;@unknown] [cite/nocite:@doe]\n\
+         #+PRINT_BIBLIOGRAPHY:",
+    )
+    .write_html_custom(&mut writer, &mut handler)
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section><p><span class=\"citation\"><a href=\"#cite-lee\">Lee (2021)</a></span> \
+         and <span class=\"citation\">(see <a href=\"#cite-doe\">Doe et al. 2020</a> p. 3; \
+         unknown)</span> </p><div class=\"bibliography\"><ol>\
+         <li id=\"cite-doe\">Doe, J., Lee, A. &amp; Roe, B. (2020). Earlier. Journal, 12(3), 45-67.</li>\
+         <li id=\"cite-lee\">Lee, A. (2021). Later. Acme.</li></ol></div></section></main>"
+    );
+}
+
+#[test]
+fn handler_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<crate::export::DefaultHtmlHandler>();
+}
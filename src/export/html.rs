@@ -3,8 +3,16 @@ use std::io::{Error, Result as IOResult, Write};
 
 use jetscii::{bytes, BytesConst};
 
-use crate::elements::{Element, Table, TableCell, TableRow, Timestamp};
-use crate::export::write_datetime;
+use std::borrow::Cow;
+
+use crate::elements::{
+    BlockSwitches, Caption, Checkbox, ColumnAlign, Element, NumberLines, Table, TableCell,
+    TableRow, Timestamp,
+};
+use crate::export::{
+    write_datetime, write_end_time, write_repeater_delay, CitationProcessor,
+    DefaultCitationProcessor,
+};
 
 /// A wrapper for escaping sensitive characters in html.
 ///
@@ -49,65 +57,292 @@ impl<S: AsRef<str>> fmt::Display for HtmlEscape<S> {
     }
 }
 
+/// Writes the `id` and `#+ATTR_HTML` attributes of an element.
+struct HtmlAttributes<'a, 'b>(&'a Element<'b>);
+
+impl fmt::Display for HtmlAttributes<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(affiliated) = self.0.affiliated() {
+            if let Some(name) = &affiliated.name {
+                write!(f, " id=\"{}\"", HtmlEscape(name))?;
+            }
+            for (key, value) in affiliated.attributes("html") {
+                write!(f, " {}=\"{}\"", HtmlEscape(key), HtmlEscape(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// block contents with coderef labels, and line numbers from the given
+// first one
+struct HtmlCode<'a>(&'a str, BlockSwitches<'a>, Option<usize>);
+
+impl fmt::Display for HtmlCode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let HtmlCode(contents, switches, start) = self;
+        let start = *start;
+
+        let contents = if switches.preserve_indent {
+            Cow::Borrowed(*contents)
+        } else {
+            remove_indentation(contents)
+        };
+
+        let width = start
+            .map(|start| {
+                (start + contents.lines().count())
+                    .saturating_sub(1)
+                    .to_string()
+                    .len()
+            })
+            .unwrap_or_default();
+
+        for (i, line) in contents.split_inclusive('\n').enumerate() {
+            if let Some(start) = start {
+                write!(
+                    f,
+                    "<span class=\"linenr\">{:>1$}: </span>",
+                    start + i,
+                    width
+                )?;
+            }
+
+            let (line, newline) = match line.strip_suffix('\n') {
+                Some(line) => (line, "\n"),
+                None => (line, ""),
+            };
+
+            if let Some((code, label)) = switches.split_label(line) {
+                write!(
+                    f,
+                    "<span id=\"coderef-{}\" class=\"coderef-off\">{}",
+                    HtmlEscape(label),
+                    HtmlEscape(code.trim_end())
+                )?;
+                if switches.retain_labels {
+                    write!(f, " ({})", HtmlEscape(label))?;
+                }
+                write!(f, "</span>{}", newline)?;
+            } else {
+                write!(f, "{}{}", HtmlEscape(line), newline)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// removes common indentation of non-blank lines
+fn remove_indentation(contents: &str) -> Cow<'_, str> {
+    let indent = |line: &str| line.len() - line.trim_start_matches(&[' ', '\t'][..]).len();
+
+    let min = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(indent)
+        .min()
+        .unwrap_or_default();
+
+    if min == 0 {
+        return Cow::Borrowed(contents);
+    }
+
+    contents
+        .split_inclusive('\n')
+        .map(|line| &line[indent(line).min(min)..])
+        .collect::<String>()
+        .into()
+}
+
+fn caption<'a>(element: &'a Element) -> Option<&'a Caption<'a>> {
+    element
+        .affiliated()
+        .and_then(|affiliated| affiliated.caption())
+}
+
+// label of a link to coderef, e.g. `[[(label)]]`
+fn coderef(path: &str) -> Option<&str> {
+    path.strip_prefix('(')?.strip_suffix(')')
+}
+
+// anchor shared by a radio target and its links, which may differ in case
+// and whitespaces
+fn radio_anchor(target: &str) -> String {
+    let words: Vec<_> = target.split_whitespace().map(str::to_lowercase).collect();
+    format!("radio-{}", words.join("-"))
+}
+
 pub trait HtmlHandler<E: From<Error>>: Default {
     fn start<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
     fn end<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
+
+    /// Whether source blocks, babel calls and their results are skipped
+    /// according to the `:exports` header argument, default is `false`.
+    fn apply_exports(&self) -> bool {
+        false
+    }
 }
 
 /// Default Html Handler
-#[derive(Default)]
-pub struct DefaultHtmlHandler;
+pub struct DefaultHtmlHandler {
+    /// Processor for rendering citations and bibliography, default is
+    /// `DefaultCitationProcessor`
+    pub citation_processor: Box<dyn CitationProcessor + Send>,
+    /// Whether to skip source blocks, babel calls and their results
+    /// according to the `:exports` header argument, default is `true`
+    pub apply_exports: bool,
+    // alignment of each column in current table
+    table_align: Vec<Option<ColumnAlign>>,
+    // index of next cell in current row
+    table_column: usize,
+    // whether each open list is a description list
+    description_lists: Vec<bool>,
+    // last line number of previous numbered block
+    last_line_number: usize,
+}
+
+impl Default for DefaultHtmlHandler {
+    fn default() -> Self {
+        DefaultHtmlHandler {
+            citation_processor: Box::new(DefaultCitationProcessor::default()),
+            apply_exports: true,
+            table_align: Vec::new(),
+            table_column: 0,
+            description_lists: Vec::new(),
+            last_line_number: 0,
+        }
+    }
+}
+
+impl DefaultHtmlHandler {
+    // first line number of a block, or `None` if it's not numbered
+    fn first_line_number(&mut self, contents: &str, switches: &BlockSwitches) -> Option<usize> {
+        let start = match switches.number_lines? {
+            NumberLines::New(start) => start,
+            NumberLines::Continued(offset) => self.last_line_number + offset,
+        };
+        self.last_line_number = (start + contents.lines().count()).saturating_sub(1);
+        Some(start)
+    }
+}
 
 impl HtmlHandler<Error> for DefaultHtmlHandler {
     fn start<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
         match element {
             // container elements
             Element::SpecialBlock(_) => (),
-            Element::QuoteBlock(_) => write!(w, "<blockquote>")?,
-            Element::CenterBlock(_) => write!(w, "<div class=\"center\">")?,
-            Element::VerseBlock(_) => write!(w, "<p class=\"verse\">")?,
+            Element::QuoteBlock(_) => write!(w, "<blockquote{}>", HtmlAttributes(element))?,
+            Element::CenterBlock(_) => {
+                write!(w, "<div class=\"center\"{}>", HtmlAttributes(element))?
+            }
+            Element::VerseBlock(_) => write!(w, "<p class=\"verse\"{}>", HtmlAttributes(element))?,
             Element::Bold => write!(w, "<b>")?,
             Element::Document { .. } => write!(w, "<main>")?,
             Element::DynBlock(_dyn_block) => (),
             Element::Headline { .. } => (),
+            Element::Inlinetask(_) => write!(w, "<div class=\"inlinetask\">")?,
             Element::List(list) => {
-                if list.ordered {
-                    write!(w, "<ol>")?;
+                self.description_lists.push(list.description);
+                if list.description {
+                    write!(w, "<dl{}>", HtmlAttributes(element))?;
+                } else if list.ordered {
+                    write!(w, "<ol{}>", HtmlAttributes(element))?;
                 } else {
-                    write!(w, "<ul>")?;
+                    write!(w, "<ul{}>", HtmlAttributes(element))?;
                 }
             }
             Element::Italic => write!(w, "<i>")?,
-            Element::ListItem(_) => write!(w, "<li>")?,
-            Element::Paragraph { .. } => write!(w, "<p>")?,
+            Element::ListItem(item) => {
+                let class = match item.checkbox {
+                    Some(Checkbox::On) => " class=\"on\"",
+                    Some(Checkbox::Off) => " class=\"off\"",
+                    Some(Checkbox::Trans) => " class=\"trans\"",
+                    None => "",
+                };
+                let description = self.description_lists.last().copied().unwrap_or_default();
+                if description {
+                    write!(w, "<dt{}>", class)?;
+                } else {
+                    write!(w, "<li{}", class)?;
+                    if let Some(counter) = item
+                        .counter
+                        .as_ref()
+                        .filter(|c| item.ordered && c.bytes().all(|b| b.is_ascii_digit()))
+                    {
+                        write!(w, " value=\"{}\"", HtmlEscape(counter))?;
+                    }
+                    write!(w, ">")?;
+                }
+                match item.checkbox {
+                    Some(Checkbox::On) => write!(w, "<input type=\"checkbox\" checked disabled> ")?,
+                    Some(_) => write!(w, "<input type=\"checkbox\" disabled> ")?,
+                    None => (),
+                }
+                match &item.tag {
+                    Some(tag) if description => write!(w, "{}</dt><dd>", HtmlEscape(tag))?,
+                    None if description => write!(w, "(no term)</dt><dd>")?,
+                    // a tag outside of description lists is kept as text
+                    Some(tag) => write!(w, "{} :: ", HtmlEscape(tag))?,
+                    None => (),
+                }
+            }
+            Element::Paragraph { .. } => {
+                if caption(element).is_some() {
+                    write!(w, "<figure{}><p>", HtmlAttributes(element))?;
+                } else {
+                    write!(w, "<p{}>", HtmlAttributes(element))?;
+                }
+            }
             Element::Section => write!(w, "<section>")?,
             Element::Strike => write!(w, "<s>")?,
             Element::Underline => write!(w, "<u>")?,
+            Element::Subscript { .. } => write!(w, "<sub>")?,
+            Element::Superscript { .. } => write!(w, "<sup>")?,
             // non-container elements
             Element::CommentBlock(_) => (),
-            Element::ExampleBlock(block) => write!(
-                w,
-                "<pre class=\"example\">{}</pre>",
-                HtmlEscape(&block.contents)
-            )?,
+            Element::ExampleBlock(block) => {
+                let contents = block.unescaped_contents();
+                let switches = block.switches();
+                let start = self.first_line_number(&contents, &switches);
+                write!(
+                    w,
+                    "<pre class=\"example\"{}>{}</pre>",
+                    HtmlAttributes(element),
+                    HtmlCode(&contents, switches, start)
+                )?
+            }
             Element::ExportBlock(block) => {
                 if block.data.eq_ignore_ascii_case("HTML") {
                     write!(w, "{}", block.contents)?
                 }
             }
             Element::SourceBlock(block) => {
+                let contents = block.unescaped_contents();
+                let switches = block.switches();
+                let start = self.first_line_number(&contents, &switches);
                 if block.language.is_empty() {
                     write!(
                         w,
-                        "<pre class=\"example\">{}</pre>",
-                        HtmlEscape(&block.contents)
+                        "<pre class=\"example\"{}>{}</pre>",
+                        HtmlAttributes(element),
+                        HtmlCode(&contents, switches, start)
                     )?;
                 } else {
+                    write!(w, "<div class=\"org-src-container\">")?;
+                    if let Some(caption) = caption(element) {
+                        write!(
+                            w,
+                            "<label class=\"org-src-name\">{}</label>",
+                            HtmlEscape(&caption.value)
+                        )?;
+                    }
                     write!(
                         w,
-                        "<div class=\"org-src-container\"><pre class=\"src src-{}\">{}</pre></div>",
+                        "<pre class=\"src src-{}\"{}>{}</pre></div>",
                         block.language,
-                        HtmlEscape(&block.contents)
+                        HtmlAttributes(element),
+                        HtmlCode(&contents, switches, start)
                     )?;
                 }
             }
@@ -119,16 +354,42 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 HtmlEscape(&inline_src.body)
             )?,
             Element::Code { value } => write!(w, "<code>{}</code>", HtmlEscape(value))?,
+            Element::Citation(citation) => {
+                self.citation_processor.write_citation(&mut w, citation)?
+            }
             Element::FnRef(_fn_ref) => (),
             Element::InlineCall(_) => (),
-            Element::Link(link) => write!(
+            Element::Link(link) => {
+                let desc = link.desc.as_ref().unwrap_or(&link.path);
+                match coderef(&link.path) {
+                    Some(label) => write!(
+                        w,
+                        "<a href=\"#coderef-{}\" class=\"coderef\">{}</a>",
+                        HtmlEscape(label),
+                        HtmlEscape(desc),
+                    )?,
+                    None => write!(
+                        w,
+                        "<a href=\"{}\">{}</a>",
+                        HtmlEscape(&link.path),
+                        HtmlEscape(desc),
+                    )?,
+                }
+            }
+            Element::LineBreak => write!(w, "<br>")?,
+            Element::Macros(_macros) => (),
+            Element::RadioTarget(radio) => write!(
                 w,
-                "<a href=\"{}\">{}</a>",
-                HtmlEscape(&link.path),
-                HtmlEscape(link.desc.as_ref().unwrap_or(&link.path)),
+                "<a id=\"{}\">{}</a>",
+                HtmlEscape(radio_anchor(&radio.target)),
+                HtmlEscape(&radio.target),
+            )?,
+            Element::RadioLink(radio) => write!(
+                w,
+                "<a href=\"#{}\">{}</a>",
+                HtmlEscape(radio_anchor(&radio.target)),
+                HtmlEscape(&radio.value),
             )?,
-            Element::Macros(_macros) => (),
-            Element::RadioTarget => (),
             Element::Snippet(snippet) => {
                 if snippet.name.eq_ignore_ascii_case("HTML") {
                     write!(w, "{}", snippet.value)?;
@@ -143,19 +404,69 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 )?;
 
                 match timestamp {
-                    Timestamp::Active { start, .. } => {
-                        write_datetime(&mut w, "&lt;", start, "&gt;")?;
+                    Timestamp::Active {
+                        start,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "&lt;", start, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "&gt;")?;
+                    }
+                    Timestamp::Inactive {
+                        start,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "[", start, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "]")?;
                     }
-                    Timestamp::Inactive { start, .. } => {
-                        write_datetime(&mut w, "[", start, "]")?;
+                    Timestamp::ActiveRange {
+                        start,
+                        end,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "&lt;", start, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write_datetime(&mut w, "&gt;&#x2013;&lt;", end, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "&gt;")?;
                     }
-                    Timestamp::ActiveRange { start, end, .. } => {
-                        write_datetime(&mut w, "&lt;", start, "&gt;&#x2013;")?;
-                        write_datetime(&mut w, "&lt;", end, "&gt;")?;
+                    Timestamp::InactiveRange {
+                        start,
+                        end,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "[", start, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write_datetime(&mut w, "]&#x2013;[", end, "")?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "]")?;
                     }
-                    Timestamp::InactiveRange { start, end, .. } => {
-                        write_datetime(&mut w, "[", start, "]&#x2013;")?;
-                        write_datetime(&mut w, "[", end, "]")?;
+                    Timestamp::ActiveTimeRange {
+                        start,
+                        end,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "&lt;", start, "")?;
+                        write_end_time(&mut w, end)?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "&gt;")?;
+                    }
+                    Timestamp::InactiveTimeRange {
+                        start,
+                        end,
+                        repeater,
+                        delay,
+                    } => {
+                        write_datetime(&mut w, "[", start, "")?;
+                        write_end_time(&mut w, end)?;
+                        write_repeater_delay(&mut w, repeater, delay)?;
+                        write!(&mut w, "]")?;
                     }
                     Timestamp::Diary { value } => {
                         write!(&mut w, "&lt;%%({})&gt;", HtmlEscape(value))?
@@ -165,40 +476,106 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
                 write!(&mut w, "</span></span>")?;
             }
             Element::Verbatim { value } => write!(&mut w, "<code>{}</code>", HtmlEscape(value))?,
+            Element::Entity(entity) => match entity.definition() {
+                Some(def) => write!(w, "{}", def.html)?,
+                None => write!(w, "\\{}", entity.name)?,
+            },
+            Element::LatexFragment(fragment) => {
+                let value = &fragment.value;
+                if value.starts_with("$$") {
+                    write!(w, "\\[{}\\]", HtmlEscape(&value[2..value.len() - 2]))?;
+                } else if value.starts_with('$') {
+                    write!(w, "\\({}\\)", HtmlEscape(&value[1..value.len() - 1]))?;
+                } else {
+                    write!(w, "{}", HtmlEscape(value))?;
+                }
+            }
             Element::FnDef(_fn_def) => (),
             Element::Clock(_clock) => (),
             Element::Comment(_) => (),
+            // diary sexps are only used by agenda, like in `ox-html`
+            Element::DiarySexp(_) => (),
             Element::FixedWidth(fixed_width) => write!(
                 w,
-                "<pre class=\"example\">{}</pre>",
+                "<pre class=\"example\"{}>{}</pre>",
+                HtmlAttributes(element),
                 HtmlEscape(&fixed_width.value)
             )?,
+            Element::LatexEnvironment(env) => write!(
+                w,
+                "<div class=\"latex-environment\"{}>{}</div>",
+                HtmlAttributes(element),
+                HtmlEscape(env.value.trim_end())
+            )?,
+            Element::Keyword(keyword) if keyword.key.eq_ignore_ascii_case("PRINT_BIBLIOGRAPHY") => {
+                self.citation_processor.write_bibliography(&mut w)?
+            }
             Element::Keyword(_keyword) => (),
             Element::Drawer(_drawer) => (),
-            Element::Rule(_) => write!(w, "<hr>")?,
+            Element::Rule(_) => write!(w, "<hr{}>", HtmlAttributes(element))?,
             Element::Cookie(cookie) => write!(w, "<code>{}</code>", cookie.value)?,
             Element::Title(title) => {
                 write!(w, "<h{}>", if title.level <= 6 { title.level } else { 6 })?;
             }
-            Element::Table(Table::TableEl { .. }) => (),
-            Element::Table(Table::Org { has_header, .. }) => {
-                write!(w, "<table>")?;
+            Element::Table(table @ Table::TableEl { .. }) => {
+                write!(w, "<table{}>", HtmlAttributes(element))?;
+                if let Some(caption) = caption(element) {
+                    write!(w, "<caption>{}</caption>", HtmlEscape(&caption.value))?;
+                }
+                for row in table.table_el_rows().unwrap_or_default() {
+                    write!(w, "<tr>")?;
+                    for cell in row {
+                        let tag = if cell.header { "th" } else { "td" };
+                        write!(w, "<{}", tag)?;
+                        if cell.rowspan > 1 {
+                            write!(w, " rowspan=\"{}\"", cell.rowspan)?;
+                        }
+                        if cell.colspan > 1 {
+                            write!(w, " colspan=\"{}\"", cell.colspan)?;
+                        }
+                        write!(w, ">{}</{}>", HtmlEscape(&cell.contents), tag)?;
+                    }
+                    write!(w, "</tr>")?;
+                }
+            }
+            Element::Table(Table::Org {
+                has_header,
+                columns,
+                ..
+            }) => {
+                self.table_align = columns.iter().map(|column| column.align).collect();
+                write!(w, "<table{}>", HtmlAttributes(element))?;
+                if let Some(caption) = caption(element) {
+                    write!(w, "<caption>{}</caption>", HtmlEscape(&caption.value))?;
+                }
                 if *has_header {
                     write!(w, "<thead>")?;
                 } else {
                     write!(w, "<tbody>")?;
                 }
             }
-            Element::TableRow(row) => match row {
-                TableRow::Body => write!(w, "<tr>")?,
-                TableRow::BodyRule => write!(w, "</tbody><tbody>")?,
-                TableRow::Header => write!(w, "<tr>")?,
-                TableRow::HeaderRule => write!(w, "</thead><tbody>")?,
-            },
-            Element::TableCell(cell) => match cell {
-                TableCell::Body => write!(w, "<td>")?,
-                TableCell::Header => write!(w, "<th>")?,
-            },
+            Element::TableRow(row) => {
+                self.table_column = 0;
+                match row {
+                    TableRow::Body => write!(w, "<tr>")?,
+                    TableRow::BodyRule => write!(w, "</tbody><tbody>")?,
+                    TableRow::Header => write!(w, "<tr>")?,
+                    TableRow::HeaderRule => write!(w, "</thead><tbody>")?,
+                }
+            }
+            Element::TableCell(cell) => {
+                let tag = match cell {
+                    TableCell::Body => "td",
+                    TableCell::Header => "th",
+                };
+                match self.table_align.get(self.table_column).copied().flatten() {
+                    Some(ColumnAlign::Left) => write!(w, "<{} class=\"org-left\">", tag)?,
+                    Some(ColumnAlign::Center) => write!(w, "<{} class=\"org-center\">", tag)?,
+                    Some(ColumnAlign::Right) => write!(w, "<{} class=\"org-right\">", tag)?,
+                    None => write!(w, "<{}>", tag)?,
+                }
+                self.table_column += 1;
+            }
         }
 
         Ok(())
@@ -215,23 +592,45 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
             Element::Document { .. } => write!(w, "</main>")?,
             Element::DynBlock(_dyn_block) => (),
             Element::Headline { .. } => (),
+            Element::Inlinetask(_) => write!(w, "</div>")?,
             Element::List(list) => {
-                if list.ordered {
+                self.description_lists.pop();
+                if list.description {
+                    write!(w, "</dl>")?;
+                } else if list.ordered {
                     write!(w, "</ol>")?;
                 } else {
                     write!(w, "</ul>")?;
                 }
             }
             Element::Italic => write!(w, "</i>")?,
-            Element::ListItem(_) => write!(w, "</li>")?,
-            Element::Paragraph { .. } => write!(w, "</p>")?,
+            Element::ListItem(_) => {
+                if self.description_lists.last().copied().unwrap_or_default() {
+                    write!(w, "</dd>")?;
+                } else {
+                    write!(w, "</li>")?;
+                }
+            }
+            Element::Paragraph { .. } => {
+                if let Some(caption) = caption(element) {
+                    write!(
+                        w,
+                        "</p><figcaption>{}</figcaption></figure>",
+                        HtmlEscape(&caption.value)
+                    )?;
+                } else {
+                    write!(w, "</p>")?;
+                }
+            }
             Element::Section => write!(w, "</section>")?,
             Element::Strike => write!(w, "</s>")?,
             Element::Underline => write!(w, "</u>")?,
+            Element::Subscript { .. } => write!(w, "</sub>")?,
+            Element::Superscript { .. } => write!(w, "</sup>")?,
             Element::Title(title) => {
                 write!(w, "</h{}>", if title.level <= 6 { title.level } else { 6 })?
             }
-            Element::Table(Table::TableEl { .. }) => (),
+            Element::Table(Table::TableEl { .. }) => write!(w, "</table>")?,
             Element::Table(Table::Org { .. }) => {
                 write!(w, "</tbody></table>")?;
             }
@@ -248,6 +647,10 @@ impl HtmlHandler<Error> for DefaultHtmlHandler {
 
         Ok(())
     }
+
+    fn apply_exports(&self) -> bool {
+        self.apply_exports
+    }
 }
 
 #[cfg(feature = "syntect")]
@@ -270,7 +673,7 @@ mod syntect_handler {
     /// use orgize::Org;
     /// use orgize::export::{DefaultHtmlHandler, SyntectHtmlHandler};
     ///
-    /// let mut handler = SyntectHtmlHandler::new(DefaultHtmlHandler);
+    /// let mut handler = SyntectHtmlHandler::new(DefaultHtmlHandler::default());
     /// let org = Org::parse("src_rust{println!(\"Hello\")}");
     ///
     /// let mut vec = vec![];
@@ -295,7 +698,7 @@ mod syntect_handler {
     ///     },
     ///     // specify theme
     ///     theme: String::from("Solarized (dark)"),
-    ///     inner: DefaultHtmlHandler,
+    ///     inner: DefaultHtmlHandler::default(),
     ///     ..Default::default()
     /// };
     ///
@@ -362,13 +765,17 @@ mod syntect_handler {
                 )?,
                 Element::SourceBlock(block) => {
                     if block.language.is_empty() {
-                        write!(w, "<pre class=\"example\">{}</pre>", block.contents)?;
+                        write!(
+                            w,
+                            "<pre class=\"example\">{}</pre>",
+                            HtmlEscape(block.unescaped_contents())
+                        )?;
                     } else {
                         write!(
                             w,
                             "<div class=\"org-src-container\"><pre class=\"src src-{}\">{}</pre></div>",
                             block.language,
-                            self.highlight(Some(&block.language), &block.contents)
+                            self.highlight(Some(&block.language), &block.unescaped_contents())
                         )?;
                     }
                 }
@@ -380,7 +787,7 @@ mod syntect_handler {
                 Element::ExampleBlock(block) => write!(
                     w,
                     "<pre class=\"example\">{}</pre>",
-                    self.highlight(None, &block.contents)
+                    self.highlight(None, &block.unescaped_contents())
                 )?,
                 _ => self.inner.start(w, element)?,
             }
@@ -390,6 +797,10 @@ mod syntect_handler {
         fn end<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E> {
             self.inner.end(w, element)
         }
+
+        fn apply_exports(&self) -> bool {
+            self.inner.apply_exports()
+        }
     }
 }
 
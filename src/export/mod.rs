@@ -1,8 +1,12 @@
 //! Export `Org` struct to various formats.
 
+mod citation;
 mod html;
 mod org;
 
+pub use citation::{
+    BibtexCitationProcessor, CitationProcessor, CitationStyle, DefaultCitationProcessor,
+};
 #[cfg(feature = "syntect")]
 pub use html::SyntectHtmlHandler;
 pub use html::{DefaultHtmlHandler, HtmlEscape, HtmlHandler};
@@ -10,7 +14,7 @@ pub use org::{DefaultOrgHandler, OrgHandler};
 
 use std::io::{Error, Write};
 
-use crate::elements::Datetime;
+use crate::elements::{Datetime, Delay, Repeater};
 
 pub(crate) fn write_datetime<W: Write>(
     mut w: W,
@@ -29,3 +33,32 @@ pub(crate) fn write_datetime<W: Write>(
     }
     write!(w, "{}", end)
 }
+
+// end time of a time range inside a single day
+pub(crate) fn write_end_time<W: Write>(mut w: W, end: &Datetime) -> Result<(), Error> {
+    if let (Some(hour), Some(minute)) = (end.hour, end.minute) {
+        write!(w, "-{:02}:{:02}", hour, minute)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn write_repeater_delay<W: Write>(
+    mut w: W,
+    repeater: &Option<Repeater>,
+    delay: &Option<Delay>,
+) -> Result<(), Error> {
+    let (before, after) = match delay {
+        Some(delay) if delay.before_repeater => (Some(delay), None),
+        delay => (None, delay.as_ref()),
+    };
+    if let Some(delay) = before {
+        write!(w, " {}", delay)?;
+    }
+    if let Some(repeater) = repeater {
+        write!(w, " {}", repeater)?;
+    }
+    if let Some(delay) = after {
+        write!(w, " {}", delay)?;
+    }
+    Ok(())
+}
@@ -1,18 +1,143 @@
 use std::io::{Error, Result as IOResult, Write};
 
-use crate::elements::{Clock, Element, Table, Timestamp};
-use crate::export::write_datetime;
+use crate::elements::table::display_width;
+use crate::elements::{
+    AffiliatedKeywords, Clock, ColumnAlign, Element, LinkFormat, PropertiesMap, Table, TableColumn,
+    TableRow, Timestamp,
+};
+use crate::export::{write_datetime, write_end_time, write_repeater_delay};
 
 pub trait OrgHandler<E: From<Error>>: Default {
     fn start<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
     fn end<W: Write>(&mut self, w: W, element: &Element) -> Result<(), E>;
 }
 
+/// Default Org Handler
+///
+/// By default, cells of "org" type tables are written as-is. Setting
+/// `align_tables` realigns table columns like `org-table-align`:
+///
+/// ```rust
+/// use orgize::{export::DefaultOrgHandler, Org};
+///
+/// let org = Org::parse("| name | qty |\n|-\n| apple | 12 |\n| 梨 | 3 |\n");
+///
+/// let mut handler = DefaultOrgHandler::default();
+/// handler.align_tables = true;
+/// let mut writer = Vec::new();
+/// org.write_org_custom(&mut writer, &mut handler).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(writer).unwrap(),
+///     "| name  | qty |\n|-------+-----|\n| apple |  12 |\n| 梨    |   3 |\n"
+/// );
+/// ```
 #[derive(Default)]
-pub struct DefaultOrgHandler;
+pub struct DefaultOrgHandler {
+    /// Whether to realign columns of "org" type tables, default is `false`
+    ///
+    /// Cells are padded to the display width of the widest cell in their
+    /// column, or to the width from `<N>` cookie if it's wider. Columns
+    /// consisting mostly of numbers are aligned to the right, unless an
+    /// alignment cookie is present. Horizontal rules are redrawn to the
+    /// column widths.
+    pub align_tables: bool,
+    // rows of current table, buffered when aligning tables
+    table: Option<AlignedTable>,
+    // special rows of current table not written yet in reverse order, and
+    // numbers of table rows written
+    special_rows: Vec<(usize, String)>,
+    rows: usize,
+}
 
 impl OrgHandler<Error> for DefaultOrgHandler {
     fn start<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+        match element {
+            Element::Table(Table::Org { special_rows, .. }) => {
+                self.special_rows = special_rows
+                    .iter()
+                    .rev()
+                    .map(|(before, row)| (*before, row.to_string()))
+                    .collect();
+                self.rows = 0;
+            }
+            Element::TableRow(_) => {
+                self.write_special_rows(&mut w, self.rows)?;
+                self.rows += 1;
+            }
+            _ => (),
+        }
+
+        if !self.align_tables {
+            return Self::write_start(w, element);
+        }
+
+        match (element, &mut self.table) {
+            (Element::Table(Table::Org { columns, .. }), _) => {
+                if let Some(affiliated) = element.affiliated() {
+                    write_affiliated(&mut w, affiliated)?;
+                }
+                self.table = Some(AlignedTable::new(columns));
+            }
+            (Element::TableRow(TableRow::Header), Some(table))
+            | (Element::TableRow(TableRow::Body), Some(table)) => table.rows.push(Some(vec![])),
+            (Element::TableRow(_), Some(table)) => table.rows.push(None),
+            (Element::TableCell(_), Some(table)) => table.cell.clear(),
+            (_, Some(table)) => Self::write_start(&mut table.cell, element)?,
+            (_, None) => Self::write_start(w, element)?,
+        }
+
+        Ok(())
+    }
+
+    fn end<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+        if let Element::Table(Table::Org { .. }) = element {
+            self.write_special_rows(&mut w, usize::MAX)?;
+        }
+
+        if !self.align_tables {
+            return Self::write_end(w, element);
+        }
+
+        match (element, &mut self.table) {
+            (Element::Table(Table::Org { .. }), Some(table)) => {
+                table.write(&mut w)?;
+                self.table = None;
+                Self::write_end(w, element)?;
+            }
+            (Element::TableRow(_), Some(_)) => (),
+            (Element::TableCell(_), Some(table)) => {
+                let cell = String::from_utf8_lossy(&table.cell).trim().to_string();
+                if let Some(Some(cells)) = table.rows.last_mut() {
+                    cells.push(cell);
+                }
+            }
+            (_, Some(table)) => Self::write_end(&mut table.cell, element)?,
+            (_, None) => Self::write_end(w, element)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl DefaultOrgHandler {
+    // writes special rows of current table before its `n`-th row
+    fn write_special_rows<W: Write>(&mut self, mut w: W, n: usize) -> IOResult<()> {
+        while matches!(self.special_rows.last(), Some((before, _)) if *before <= n) {
+            let (_, row) = self.special_rows.pop().unwrap();
+            match &mut self.table {
+                Some(table) => table.push_special_row(&row),
+                None => writeln!(w, "{}", row)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_start<W: Write>(mut w: W, element: &Element) -> IOResult<()> {
+        if let Some(affiliated) = element.affiliated() {
+            write_affiliated(&mut w, affiliated)?;
+        }
+
         match element {
             // container elements
             Element::SpecialBlock(block) => {
@@ -32,7 +157,15 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write_blank_lines(&mut w, block.pre_blank)?;
             }
             Element::Bold => write!(w, "*")?,
-            Element::Document { pre_blank } => {
+            Element::Document {
+                pre_blank,
+                properties_pre_blank,
+                properties,
+            } => {
+                if !properties.is_empty() {
+                    write_blank_lines(&mut w, *properties_pre_blank)?;
+                    write_properties(&mut w, properties)?;
+                }
                 write_blank_lines(w, *pre_blank)?;
             }
             Element::DynBlock(dyn_block) => {
@@ -43,18 +176,31 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write_blank_lines(&mut w, dyn_block.pre_blank + 1)?;
             }
             Element::Headline { .. } => (),
+            Element::Inlinetask(_) => (),
             Element::List(_list) => (),
             Element::Italic => write!(w, "/")?,
             Element::ListItem(list_item) => {
                 for _ in 0..list_item.indent {
                     write!(&mut w, " ")?;
                 }
-                write!(&mut w, "{}", list_item.bullet)?;
+                write!(&mut w, "{}", list_item.header)?;
             }
             Element::Paragraph { .. } => (),
             Element::Section => (),
             Element::Strike => write!(w, "+")?,
             Element::Underline => write!(w, "_")?,
+            Element::Subscript { brackets } => {
+                write!(w, "_")?;
+                if *brackets {
+                    write!(w, "{{")?;
+                }
+            }
+            Element::Superscript { brackets } => {
+                write!(w, "^")?;
+                if *brackets {
+                    write!(w, "{{")?;
+                }
+            }
             Element::Drawer(drawer) => {
                 writeln!(&mut w, ":{}:", drawer.name)?;
                 write_blank_lines(&mut w, drawer.pre_blank)?;
@@ -95,6 +241,32 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 }
                 write!(&mut w, "{{{}}}", inline_src.body)?;
             }
+            Element::Citation(citation) => {
+                write!(w, "[cite")?;
+                if let Some(style) = &citation.style {
+                    write!(w, "/{}", style)?;
+                }
+                write!(w, ":")?;
+                if let Some(prefix) = &citation.prefix {
+                    write!(w, "{};", prefix)?;
+                }
+                for (i, reference) in citation.references.iter().enumerate() {
+                    if i > 0 {
+                        write!(w, ";")?;
+                    }
+                    write!(
+                        w,
+                        "{}@{}{}",
+                        reference.prefix.as_deref().unwrap_or_default(),
+                        reference.key,
+                        reference.suffix.as_deref().unwrap_or_default()
+                    )?;
+                }
+                if let Some(suffix) = &citation.suffix {
+                    write!(w, ";{}", suffix)?;
+                }
+                write!(w, "]")?;
+            }
             Element::Code { value } => write!(w, "~{}~", value)?,
             Element::FnRef(fn_ref) => {
                 write!(&mut w, "[fn:{}", fn_ref.label)?;
@@ -113,16 +285,29 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                     write!(&mut w, "[{}]", header)?;
                 }
             }
-            Element::Link(link) => {
-                write!(&mut w, "[[{}]", link.path)?;
-                if let Some(desc) = &link.desc {
-                    write!(&mut w, "[{}]", desc)?;
+            Element::Link(link) => match link.format {
+                LinkFormat::Bracket => {
+                    write!(&mut w, "[[{}]", link.path)?;
+                    if let Some(desc) = &link.desc {
+                        write!(&mut w, "[{}]", desc)?;
+                    }
+                    write!(&mut w, "]")?;
                 }
-                write!(&mut w, "]")?;
-            }
+                LinkFormat::Plain => write!(&mut w, "{}", link.path)?,
+                LinkFormat::Angle => write!(&mut w, "<{}>", link.path)?,
+            },
+            Element::LineBreak => write!(w, "\\\\")?,
             Element::Macros(_macros) => (),
-            Element::RadioTarget => (),
+            Element::RadioTarget(radio) => write!(w, "<<<{}>>>", radio.target)?,
+            Element::RadioLink(radio) => write!(w, "{}", radio.value)?,
             Element::Snippet(snippet) => write!(w, "@@{}:{}@@", snippet.name, snippet.value)?,
+            Element::Entity(entity) => {
+                write!(w, "\\{}", entity.name)?;
+                if entity.brackets {
+                    write!(w, "{{}}")?;
+                }
+            }
+            Element::LatexFragment(fragment) => write!(w, "{}", fragment.value)?,
             Element::Target(_target) => (),
             Element::Text { value } => write!(w, "{}", value)?,
             Element::Timestamp(timestamp) => {
@@ -137,21 +322,17 @@ impl OrgHandler<Error> for DefaultOrgHandler {
 
                 match clock {
                     Clock::Closed {
-                        start,
-                        end,
                         duration,
                         post_blank,
                         ..
                     } => {
-                        write_datetime(&mut w, "[", &start, "]--")?;
-                        write_datetime(&mut w, "[", &end, "]")?;
+                        write_timestamp(&mut w, &clock.value())?;
                         writeln!(&mut w, " => {}", duration)?;
                         write_blank_lines(&mut w, *post_blank)?;
                     }
-                    Clock::Running {
-                        start, post_blank, ..
-                    } => {
-                        write_datetime(&mut w, "[", &start, "]\n")?;
+                    Clock::Running { post_blank, .. } => {
+                        write_timestamp(&mut w, &clock.value())?;
+                        writeln!(&mut w)?;
                         write_blank_lines(&mut w, *post_blank)?;
                     }
                 }
@@ -160,16 +341,25 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write!(w, "{}", comment.value)?;
                 write_blank_lines(&mut w, comment.post_blank)?;
             }
+            Element::DiarySexp(sexp) => {
+                writeln!(&mut w, "%%{}", sexp.value)?;
+                write_blank_lines(&mut w, sexp.post_blank)?;
+            }
             Element::FixedWidth(fixed_width) => {
                 write!(&mut w, "{}", fixed_width.value)?;
                 write_blank_lines(&mut w, fixed_width.post_blank)?;
             }
+            Element::LatexEnvironment(env) => {
+                write!(&mut w, "{}", env.value)?;
+                write_blank_lines(&mut w, env.post_blank)?;
+            }
             Element::Keyword(keyword) => {
-                write!(&mut w, "#+{}", keyword.key)?;
-                if let Some(optional) = &keyword.optional {
-                    write!(&mut w, "[{}]", optional)?;
-                }
-                writeln!(&mut w, ": {}", keyword.value)?;
+                write_keyword(
+                    &mut w,
+                    &keyword.key,
+                    keyword.optional.as_deref(),
+                    &keyword.value,
+                )?;
                 write_blank_lines(&mut w, keyword.post_blank)?;
             }
             Element::Rule(rule) => {
@@ -189,15 +379,21 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 }
                 write!(&mut w, " ")?;
             }
-            Element::Table(_) => (),
-            Element::TableRow(_) => (),
-            Element::TableCell(_) => (),
+            Element::Table(Table::Org { .. }) => (),
+            Element::Table(Table::TableEl { value, .. }) => write!(w, "{}", value)?,
+            Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => {
+                write!(w, "|")?
+            }
+            Element::TableRow(TableRow::HeaderRule) | Element::TableRow(TableRow::BodyRule) => {
+                writeln!(w, "|-")?
+            }
+            Element::TableCell(_) => write!(w, " ")?,
         }
 
         Ok(())
     }
 
-    fn end<W: Write>(&mut self, mut w: W, element: &Element) -> IOResult<()> {
+    fn write_end<W: Write>(mut w: W, element: &Element) -> IOResult<()> {
         match element {
             // container elements
             Element::SpecialBlock(block) => {
@@ -223,17 +419,31 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                 write_blank_lines(w, dyn_block.post_blank)?;
             }
             Element::Headline { .. } => (),
+            Element::Inlinetask(inlinetask) => {
+                if inlinetask.has_end {
+                    for _ in 0..inlinetask.level {
+                        write!(&mut w, "*")?;
+                    }
+                    writeln!(&mut w, " END")?;
+                }
+                write_blank_lines(&mut w, inlinetask.post_blank)?;
+            }
             Element::List(list) => {
                 write_blank_lines(w, list.post_blank)?;
             }
             Element::Italic => write!(w, "/")?,
             Element::ListItem(_) => (),
-            Element::Paragraph { post_blank } => {
+            Element::Paragraph { post_blank, .. } => {
                 write_blank_lines(w, post_blank + 1)?;
             }
             Element::Section => (),
             Element::Strike => write!(w, "+")?,
             Element::Underline => write!(w, "_")?,
+            Element::Subscript { brackets } | Element::Superscript { brackets } => {
+                if *brackets {
+                    write!(w, "}}")?;
+                }
+            }
             Element::Drawer(drawer) => {
                 writeln!(&mut w, ":END:")?;
                 write_blank_lines(&mut w, drawer.post_blank)?;
@@ -268,22 +478,24 @@ impl OrgHandler<Error> for DefaultOrgHandler {
                     writeln!(&mut w)?;
                 }
                 if !title.properties.is_empty() {
-                    writeln!(&mut w, ":PROPERTIES:")?;
-                    for (key, value) in title.properties.iter() {
-                        writeln!(&mut w, ":{}: {}", key, value)?;
-                    }
-                    writeln!(&mut w, ":END:")?;
+                    write_properties(&mut w, &title.properties)?;
                 }
                 write_blank_lines(&mut w, title.post_blank)?;
             }
-            Element::Table(Table::Org { post_blank, .. }) => {
+            Element::Table(Table::Org {
+                tblfm, post_blank, ..
+            }) => {
+                for formula in tblfm {
+                    writeln!(w, "#+TBLFM: {}", formula)?;
+                }
                 write_blank_lines(w, *post_blank)?;
             }
             Element::Table(Table::TableEl { post_blank, .. }) => {
                 write_blank_lines(w, *post_blank)?;
             }
+            Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => writeln!(w)?,
             Element::TableRow(_) => (),
-            Element::TableCell(_) => (),
+            Element::TableCell(_) => write!(w, " |")?,
             // non-container elements
             _ => debug_assert!(!element.is_container()),
         }
@@ -292,6 +504,113 @@ impl OrgHandler<Error> for DefaultOrgHandler {
     }
 }
 
+// rows of an "org" type table, `None` for horizontal rules
+struct AlignedTable {
+    columns: Vec<TableColumn>,
+    rows: Vec<Option<Vec<String>>>,
+    // indices of cookie rows and column group rows
+    special_rows: Vec<usize>,
+    // contents of current cell
+    cell: Vec<u8>,
+}
+
+impl AlignedTable {
+    fn new(columns: &[TableColumn]) -> Self {
+        AlignedTable {
+            columns: columns.to_vec(),
+            rows: vec![],
+            special_rows: vec![],
+            cell: vec![],
+        }
+    }
+
+    fn push_special_row(&mut self, row: &str) {
+        let cells = row
+            .split_terminator('|')
+            .skip(1)
+            .map(|cell| cell.trim().to_string())
+            .collect();
+        self.special_rows.push(self.rows.len());
+        self.rows.push(Some(cells));
+    }
+
+    fn write<W: Write>(&self, mut w: W) -> IOResult<()> {
+        let count = self
+            .rows
+            .iter()
+            .flatten()
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+
+        let mut widths = vec![1; count];
+        let mut aligns = vec![ColumnAlign::Left; count];
+
+        for (i, (width, align)) in widths.iter_mut().zip(aligns.iter_mut()).enumerate() {
+            let cells = self.rows.iter().flatten().filter_map(|cells| cells.get(i));
+            for cell in cells.clone() {
+                *width = (*width).max(display_width(cell));
+            }
+
+            let column = self.columns.get(i);
+            if let Some(min) = column.and_then(|c| c.width) {
+                *width = (*width).max(min);
+            }
+
+            if let Some(explicit) = column.and_then(|c| c.align) {
+                *align = explicit;
+            } else {
+                let cells: Vec<_> = self
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !self.special_rows.contains(i))
+                    .filter_map(|(_, row)| row.as_ref())
+                    .filter_map(|cells| cells.get(i))
+                    .filter(|cell| !cell.is_empty())
+                    .collect();
+                let numbers = cells.iter().filter(|cell| is_number(cell)).count();
+                // same as the default `org-table-number-fraction`
+                if !cells.is_empty() && numbers * 2 >= cells.len() {
+                    *align = ColumnAlign::Right;
+                }
+            }
+        }
+
+        for row in &self.rows {
+            match row {
+                Some(cells) => {
+                    write!(w, "|")?;
+                    for (i, (&width, align)) in widths.iter().zip(&aligns).enumerate() {
+                        let cell = cells.get(i).map(String::as_str).unwrap_or_default();
+                        let pad = width - display_width(cell);
+                        let (left, right) = match align {
+                            ColumnAlign::Left => (0, pad),
+                            ColumnAlign::Center => (pad / 2, pad - pad / 2),
+                            ColumnAlign::Right => (pad, 0),
+                        };
+                        write!(w, " {:l$}{}{:r$} |", "", cell, "", l = left, r = right)?;
+                    }
+                    writeln!(w)?;
+                }
+                None => {
+                    let dashes: Vec<_> = widths.iter().map(|width| "-".repeat(width + 2)).collect();
+                    writeln!(w, "|{}|", dashes.join("+"))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// whether a cell looks like a number, e.g. `-1.5`, `1e3` or `50%`
+fn is_number(cell: &str) -> bool {
+    let number = cell.strip_suffix('%').unwrap_or(cell);
+    number.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+        && number.parse::<f64>().is_ok()
+}
+
 fn write_blank_lines<W: Write>(mut w: W, count: usize) -> Result<(), Error> {
     for _ in 0..count {
         writeln!(w)?;
@@ -299,23 +618,157 @@ fn write_blank_lines<W: Write>(mut w: W, count: usize) -> Result<(), Error> {
     Ok(())
 }
 
+fn write_properties<W: Write>(mut w: W, properties: &PropertiesMap) -> Result<(), Error> {
+    writeln!(&mut w, ":PROPERTIES:")?;
+    for (key, value) in properties.iter() {
+        if value.is_empty() {
+            writeln!(&mut w, ":{}:", key)?;
+        } else {
+            writeln!(&mut w, ":{}: {}", key, value)?;
+        }
+    }
+    writeln!(&mut w, ":END:")
+}
+
+fn write_keyword<W: Write>(
+    mut w: W,
+    key: &str,
+    optional: Option<&str>,
+    value: &str,
+) -> Result<(), Error> {
+    write!(&mut w, "#+{}", key)?;
+    if let Some(optional) = optional {
+        write!(&mut w, "[{}]", optional)?;
+    }
+    writeln!(&mut w, ": {}", value)
+}
+
+fn write_affiliated<W: Write>(mut w: W, affiliated: &AffiliatedKeywords) -> Result<(), Error> {
+    if !affiliated.keywords.is_empty() {
+        for keyword in &affiliated.keywords {
+            write_keyword(
+                &mut w,
+                &keyword.key,
+                keyword.optional.as_deref(),
+                &keyword.value,
+            )?;
+        }
+        return Ok(());
+    }
+
+    if let Some(name) = &affiliated.name {
+        write_keyword(&mut w, "NAME", None, name)?;
+    }
+    for caption in &affiliated.caption {
+        write_keyword(&mut w, "CAPTION", caption.short.as_deref(), &caption.value)?;
+    }
+    for header in &affiliated.header {
+        write_keyword(&mut w, "HEADER", None, header)?;
+    }
+    if let Some(plot) = &affiliated.plot {
+        write_keyword(&mut w, "PLOT", None, plot)?;
+    }
+    if let Some(results) = &affiliated.results {
+        write_keyword(&mut w, "RESULTS", results.hash.as_deref(), &results.value)?;
+    }
+    for (backend, value) in &affiliated.attributes {
+        write!(&mut w, "#+ATTR_{}", backend)?;
+        writeln!(&mut w, ": {}", value)?;
+    }
+    Ok(())
+}
+
 fn write_timestamp<W: Write>(mut w: W, timestamp: &Timestamp) -> Result<(), Error> {
     match timestamp {
-        Timestamp::Active { start, .. } => {
-            write_datetime(w, "<", start, ">")?;
+        Timestamp::Active {
+            start,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "<", start, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, ">")?;
         }
-        Timestamp::Inactive { start, .. } => {
-            write_datetime(w, "[", start, "]")?;
+        Timestamp::Inactive {
+            start,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "[", start, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, "]")?;
         }
-        Timestamp::ActiveRange { start, end, .. } => {
-            write_datetime(&mut w, "<", start, ">--")?;
-            write_datetime(&mut w, "<", end, ">")?;
+        Timestamp::ActiveRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "<", start, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write_datetime(&mut w, ">--<", end, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, ">")?;
         }
-        Timestamp::InactiveRange { start, end, .. } => {
-            write_datetime(&mut w, "[", start, "]--")?;
-            write_datetime(&mut w, "[", end, "]")?;
+        Timestamp::InactiveRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "[", start, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write_datetime(&mut w, "]--[", end, "")?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, "]")?;
+        }
+        Timestamp::ActiveTimeRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "<", start, "")?;
+            write_end_time(&mut w, end)?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, ">")?;
+        }
+        Timestamp::InactiveTimeRange {
+            start,
+            end,
+            repeater,
+            delay,
+        } => {
+            write_datetime(&mut w, "[", start, "")?;
+            write_end_time(&mut w, end)?;
+            write_repeater_delay(&mut w, repeater, delay)?;
+            write!(w, "]")?;
         }
         Timestamp::Diary { value } => write!(w, "<%%({})>", value)?,
     }
     Ok(())
 }
+
+#[test]
+fn align_tables() {
+    use crate::Org;
+
+    let org = Org::parse(
+        "| <c> | <6> |\n|x|1.5|\n|-\n| / | <> |\n| long cell | -2 |\n|-\n#+TBLFM: $2=$2\n\ntext\n",
+    );
+
+    let mut handler = DefaultOrgHandler::default();
+    handler.align_tables = true;
+    let mut writer = Vec::new();
+    org.write_org_custom(&mut writer, &mut handler).unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "|    <c>    |    <6> |\n\
+         |     x     |    1.5 |\n\
+         |-----------+--------|\n\
+         |     /     |     <> |\n\
+         | long cell |     -2 |\n\
+         #+TBLFM: $2=$2\n\ntext\n"
+    );
+}
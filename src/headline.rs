@@ -1,11 +1,12 @@
 use indextree::NodeId;
 use std::borrow::Cow;
+use std::iter::successors;
 use std::ops::RangeInclusive;
 use std::usize;
 
 use crate::{
     config::ParseConfig,
-    elements::{Element, Title},
+    elements::{Element, HeaderArgs, PropertiesMap, Title},
     parsers::{parse_container, Container, OwnedArena},
     validate::{ValidationError, ValidationResult},
     Org,
@@ -42,6 +43,57 @@ impl Document {
         self.sec_n
     }
 
+    /// Returns the properties of this document.
+    ///
+    /// File-wide defaults from `#+PROPERTY` keywords come first, followed
+    /// by the property drawer at the beginning of document, so that
+    /// `PropertiesMap::get` applies both of them in order.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     r#":PROPERTIES:
+    /// :ID: 2c9d
+    /// :header-args+: :results silent
+    /// :END:
+    /// #+TITLE: Notes
+    /// #+PROPERTY: header-args :exports both
+    /// "#,
+    /// );
+    ///
+    /// let properties = org.document().properties(&org);
+    ///
+    /// assert_eq!(properties.get("ID").as_deref(), Some("2c9d"));
+    /// assert_eq!(
+    ///     properties.get("header-args").as_deref(),
+    ///     Some(":exports both :results silent")
+    /// );
+    /// ```
+    pub fn properties<'a>(self, org: &Org<'a>) -> PropertiesMap<'a> {
+        let mut properties: PropertiesMap<'a> = org
+            .keywords()
+            .filter(|keyword| keyword.key.eq_ignore_ascii_case("PROPERTY"))
+            .map(|keyword| {
+                let value = keyword.value.trim();
+                let (key, value) = match value.find(char::is_whitespace) {
+                    Some(i) => (&value[0..i], value[i..].trim()),
+                    None => (value, ""),
+                };
+                (key.to_string().into(), value.to_string().into())
+            })
+            .collect();
+
+        if let Element::Document {
+            properties: drawer, ..
+        } = &org[self.doc_n]
+        {
+            properties.pairs.extend(drawer.iter().cloned());
+        }
+
+        properties
+    }
+
     /// Returns an iterator of this document's children.
     ///
     /// ```rust
@@ -363,7 +415,11 @@ impl Headline {
     pub fn new<'a>(ttl: Title<'a>, org: &mut Org<'a>) -> Headline {
         let lvl = ttl.level;
         let hdl_n = org.arena.new_node(Element::Headline { level: ttl.level });
-        let ttl_n = org.arena.new_node(Element::Document { pre_blank: 0 }); // placeholder
+        let ttl_n = org.arena.new_node(Element::Document {
+            pre_blank: 0,
+            properties_pre_blank: 0,
+            properties: PropertiesMap::new(),
+        }); // placeholder
         hdl_n.append(ttl_n, &mut org.arena);
 
         match ttl.raw {
@@ -438,6 +494,53 @@ impl Headline {
         }
     }
 
+    /// Returns the value of property `key` of this headline, inheriting
+    /// values from its ancestors and document properties, like
+    /// `org-entry-get` with inheritance.
+    ///
+    /// `KEY+` properties accumulate onto the inherited value, while `KEY`
+    /// properties override it.
+    ///
+    /// ```rust
+    /// # use orgize::Org;
+    /// #
+    /// let org = Org::parse(
+    ///     r#"#+PROPERTY: var foo=1
+    /// * h1
+    /// :PROPERTIES:
+    /// :var+: bar=2
+    /// :END:
+    /// ** h1_1
+    /// :PROPERTIES:
+    /// :VAR+: baz=3
+    /// :END:
+    /// * h2
+    /// :PROPERTIES:
+    /// :var: qux=4
+    /// :END:
+    /// "#,
+    /// );
+    ///
+    /// let h1_1 = org.headlines().nth(1).unwrap();
+    /// let h2 = org.headlines().nth(2).unwrap();
+    ///
+    /// assert_eq!(h1_1.property(&org, "var").as_deref(), Some("foo=1 bar=2 baz=3"));
+    /// assert_eq!(h2.property(&org, "var").as_deref(), Some("qux=4"));
+    /// assert_eq!(h2.property(&org, "missing"), None);
+    /// ```
+    pub fn property<'a>(self, org: &Org<'a>, key: &str) -> Option<Cow<'a, str>> {
+        let mut properties = org.document().properties(org);
+
+        let ancestors: Vec<_> = successors(Some(self), |hdl| hdl.parent(org)).collect();
+        for hdl in ancestors.into_iter().rev() {
+            properties
+                .pairs
+                .extend(hdl.title(org).properties.iter().cloned());
+        }
+
+        properties.get(key)
+    }
+
     /// Returns a mutual reference to the title element of this headline.
     ///
     /// Don't change the level and content of the `&mut Titile` directly.
@@ -1216,4 +1319,180 @@ impl Org<'_> {
                 _ => None,
             })
     }
+
+    /// Returns header arguments of a source block, an inline source block
+    /// or a babel call, or `None` if `node` is not one of them.
+    ///
+    /// Arguments of the element itself are preceded by inherited
+    /// defaults, from `header-args` property then `header-args:LANG`
+    /// property of its headline or the document.
+    ///
+    /// ```rust
+    /// use orgize::{elements::Exports, Element, Org};
+    ///
+    /// let org = Org::parse(
+    ///     r#"#+PROPERTY: header-args :exports both :results output
+    /// * Headline
+    /// :PROPERTIES:
+    /// :header-args:python: :session py
+    /// :END:
+    /// #+BEGIN_SRC python :results silent :exports code
+    /// print(1)
+    /// #+END_SRC
+    /// "#,
+    /// );
+    ///
+    /// let node = org
+    ///     .arena()
+    ///     .iter()
+    ///     .find(|node| matches!(node.get(), Element::SourceBlock(_)))
+    ///     .and_then(|node| org.arena().get_node_id(node))
+    ///     .unwrap();
+    /// let args = org.header_args(node).unwrap();
+    ///
+    /// assert_eq!(args.session(), Some("py"));
+    /// assert_eq!(args.exports(), Some(Exports::Code));
+    /// assert_eq!(args.results().collection, Some("output"));
+    /// assert_eq!(args.results().handling, Some("silent"));
+    /// ```
+    pub fn header_args(&self, node: NodeId) -> Option<HeaderArgs<'_>> {
+        self.header_args_with(node, &self.document().properties(self))
+    }
+
+    // like `header_args`, but with precomputed document properties
+    pub(crate) fn header_args_with(
+        &self,
+        node: NodeId,
+        document: &PropertiesMap<'_>,
+    ) -> Option<HeaderArgs<'_>> {
+        let (language, args) = match &self[node] {
+            Element::SourceBlock(block) => (Some(&*block.language), block.header_args()),
+            Element::InlineSrc(src) => (Some(&*src.lang), src.header_args()),
+            Element::BabelCall(call) => (None, call.header_args()),
+            Element::InlineCall(call) => (None, call.header_args()),
+            _ => return None,
+        };
+
+        // document properties, overridden by properties of each ancestor
+        // headline from the outermost one
+        let mut properties = document.clone();
+        let headlines: Vec<_> = node
+            .ancestors(&self.arena)
+            .filter_map(|n| match self[n] {
+                Element::Headline { level } => Some(Headline::from_node(n, level, self)),
+                _ => None,
+            })
+            .collect();
+        for headline in headlines.into_iter().rev() {
+            properties
+                .pairs
+                .extend(headline.title(self).properties.iter().cloned());
+        }
+
+        let mut inherited = HeaderArgs::default();
+        let keys = Some("header-args".to_string())
+            .into_iter()
+            .chain(language.map(|language| format!("header-args:{}", language)));
+        for key in keys {
+            if let Some(value) = properties.get(&key) {
+                inherited.extend(HeaderArgs::parse(&value).into_owned());
+            }
+        }
+        inherited.extend(args);
+
+        Some(inherited)
+    }
+
+    /// Returns the results of a source block or a babel call, or `None` if
+    /// it has no results or `node` is not one of them.
+    ///
+    /// Results are the element marked with `#+RESULTS:` right after the
+    /// block or call, or anywhere in the document if the marker refers to
+    /// the block name. For results which don't accept affiliated keywords,
+    /// like clocks and comments, the element following the `#+RESULTS:`
+    /// keyword is returned.
+    ///
+    /// The link is looked up on each call instead of being recorded at
+    /// parse time: named results may appear anywhere in the document, even
+    /// before the block, and the tree can be modified after parsing, e.g.
+    /// with [`Org::arena_mut`] or [`Headline::detach`], which would leave
+    /// a recorded link dangling.
+    ///
+    /// ```rust
+    /// use orgize::{Element, Org};
+    ///
+    /// let org = Org::parse(
+    ///     r#"#+NAME: answer
+    /// #+BEGIN_SRC python
+    /// return 42
+    /// #+END_SRC
+    ///
+    /// #+RESULTS: answer
+    /// : 42
+    /// "#,
+    /// );
+    ///
+    /// let block = org
+    ///     .arena()
+    ///     .iter()
+    ///     .find(|node| matches!(node.get(), Element::SourceBlock(_)))
+    ///     .and_then(|node| org.arena().get_node_id(node))
+    ///     .unwrap();
+    /// let results = org.results(block).unwrap();
+    ///
+    /// assert!(matches!(&org[results], Element::FixedWidth(f) if f.value == ": 42\n"));
+    /// ```
+    pub fn results(&self, node: NodeId) -> Option<NodeId> {
+        self.results_with(node, |name| {
+            self.root
+                .descendants(&self.arena)
+                .filter_map(|n| self.results_marker(n))
+                .find(|&(value, _)| value == name)
+                .map(|(_, results)| results)
+        })
+    }
+
+    // like `results`, but looks up results referring to the block name
+    // with `find_named`
+    pub(crate) fn results_with(
+        &self,
+        node: NodeId,
+        find_named: impl FnOnce(&str) -> Option<NodeId>,
+    ) -> Option<NodeId> {
+        let element = &self[node];
+        let name = element.affiliated().and_then(|a| a.name.as_deref());
+        let call = match element {
+            Element::SourceBlock(_) => None,
+            Element::BabelCall(call) => Some(call.call()),
+            _ => return None,
+        };
+
+        if let Some((value, results)) = self.arena[node]
+            .next_sibling()
+            .and_then(|next| self.results_marker(next))
+        {
+            if value.is_empty() || Some(value) == name || Some(value) == call {
+                return Some(results);
+            }
+        }
+
+        find_named(name?)
+    }
+
+    // value of the results marker at `node`, and the marked element
+    pub(crate) fn results_marker(&self, node: NodeId) -> Option<(&str, NodeId)> {
+        match &self[node] {
+            Element::Keyword(keyword)
+                if keyword.key.eq_ignore_ascii_case("RESULTS")
+                    || keyword.key.eq_ignore_ascii_case("RESULT") =>
+            {
+                let next = self.arena[node].next_sibling()?;
+                Some((keyword.value.trim(), next))
+            }
+            element => {
+                let results = element.affiliated()?.results.as_ref()?;
+                Some((results.value.trim(), node))
+            }
+        }
+    }
 }
@@ -221,6 +221,7 @@
 //!
 //! MIT
 
+pub mod bibtex;
 mod config;
 pub mod elements;
 pub mod export;
@@ -228,6 +229,8 @@ mod headline;
 mod org;
 mod parse;
 mod parsers;
+mod table;
+mod tangle;
 mod validate;
 
 // Re-export of the indextree crate.
@@ -235,10 +238,12 @@ pub use indextree;
 #[cfg(feature = "syntect")]
 pub use syntect;
 
-pub use config::ParseConfig;
+pub use config::{ParseConfig, UseSubSuperscripts};
 pub use elements::Element;
 pub use headline::{Document, Headline};
 pub use org::{Event, Org};
+pub use table::{FormulaError, OrgTable};
+pub use tangle::TangledFile;
 pub use validate::ValidationError;
 
 #[cfg(feature = "wasm")]
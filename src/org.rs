@@ -1,12 +1,15 @@
 use indextree::{Arena, NodeEdge, NodeId};
+use std::collections::{HashMap, HashSet};
 use std::io::{Error, Write};
 use std::ops::{Index, IndexMut};
 
 use crate::{
     config::{ParseConfig, DEFAULT_CONFIG},
-    elements::{Element, Keyword},
+    elements::{Element, Exports, Keyword, PropertiesMap},
     export::{DefaultHtmlHandler, DefaultOrgHandler, HtmlHandler, OrgHandler},
-    parsers::{blank_lines_count, parse_container, Container, OwnedArena},
+    parsers::{
+        parse_container, parse_document_properties, parse_radio_links, Container, OwnedArena,
+    },
 };
 
 pub struct Org<'a> {
@@ -24,7 +27,11 @@ impl<'a> Org<'a> {
     /// Creates a new empty `Org` struct.
     pub fn new() -> Org<'static> {
         let mut arena = Arena::new();
-        let root = arena.new_node(Element::Document { pre_blank: 0 });
+        let root = arena.new_node(Element::Document {
+            pre_blank: 0,
+            properties_pre_blank: 0,
+            properties: PropertiesMap::new(),
+        });
         Org { arena, root }
     }
 
@@ -41,8 +48,12 @@ impl<'a> Org<'a> {
     /// Parses string `text` into `Org` struct with custom `ParseConfig`.
     pub fn parse_custom(text: &'a str, config: &ParseConfig) -> Org<'a> {
         let mut arena = Arena::new();
-        let (text, pre_blank) = blank_lines_count(text);
-        let root = arena.new_node(Element::Document { pre_blank });
+        let (text, (properties_pre_blank, properties, pre_blank)) = parse_document_properties(text);
+        let root = arena.new_node(Element::Document {
+            pre_blank,
+            properties_pre_blank,
+            properties,
+        });
         let mut org = Org { arena, root };
 
         parse_container(
@@ -54,6 +65,8 @@ impl<'a> Org<'a> {
             config,
         );
 
+        parse_radio_links(&mut org.arena, org.root);
+
         org.debug_validate();
 
         org
@@ -62,8 +75,13 @@ impl<'a> Org<'a> {
     /// Likes `parse_custom`, but accepts `String`.
     pub fn parse_string_custom(text: String, config: &ParseConfig) -> Org<'static> {
         let mut arena = Arena::new();
-        let (text, pre_blank) = blank_lines_count(&text);
-        let root = arena.new_node(Element::Document { pre_blank });
+        let (text, (properties_pre_blank, properties, pre_blank)) =
+            parse_document_properties(&text);
+        let root = arena.new_node(Element::Document {
+            pre_blank,
+            properties_pre_blank,
+            properties: properties.into_owned(),
+        });
         let mut org = Org { arena, root };
 
         parse_container(
@@ -75,6 +93,8 @@ impl<'a> Org<'a> {
             config,
         );
 
+        parse_radio_links(&mut org.arena, org.root);
+
         org.debug_validate();
 
         org
@@ -114,32 +134,88 @@ impl<'a> Org<'a> {
     where
         W: Write,
     {
-        self.write_html_custom(writer, &mut DefaultHtmlHandler)
+        self.write_html_custom(writer, &mut DefaultHtmlHandler::default())
     }
 
     /// Writes an `Org` struct as html format with custom `HtmlHandler`.
+    ///
+    /// If [`HtmlHandler::apply_exports`] returns `true`, source blocks,
+    /// babel calls and their results are skipped according to the
+    /// `:exports` header argument, which defaults to `code` like in org
+    /// mode, so results are skipped if it's absent.
     pub fn write_html_custom<W, H, E>(&self, mut writer: W, handler: &mut H) -> Result<(), E>
     where
         W: Write,
         E: From<Error>,
         H: HtmlHandler<E>,
     {
-        for event in self.iter() {
-            match event {
-                Event::Start(element) => handler.start(&mut writer, element)?,
-                Event::End(element) => handler.end(&mut writer, element)?,
+        let hidden = if handler.apply_exports() {
+            self.hidden_by_exports()
+        } else {
+            HashSet::new()
+        };
+        let mut edges = self.root.traverse(&self.arena);
+
+        while let Some(edge) = edges.next() {
+            match edge {
+                NodeEdge::Start(node) if hidden.contains(&node) => {
+                    let end = NodeEdge::End(node);
+                    edges.by_ref().find(|edge| *edge == end);
+                }
+                NodeEdge::Start(node) => handler.start(&mut writer, &self[node])?,
+                NodeEdge::End(node) => handler.end(&mut writer, &self[node])?,
             }
         }
 
         Ok(())
     }
 
+    // source blocks, babel calls and results excluded from export
+    fn hidden_by_exports(&self) -> HashSet<NodeId> {
+        let mut hidden = HashSet::new();
+
+        let properties = self.document().properties(self);
+        // results referring to block names, the first one of each name wins
+        let mut named = HashMap::new();
+        for (value, results) in self
+            .root
+            .descendants(&self.arena)
+            .filter_map(|node| self.results_marker(node))
+        {
+            named.entry(value).or_insert(results);
+        }
+
+        for node in self.root.descendants(&self.arena) {
+            if !matches!(self[node], Element::SourceBlock(_) | Element::BabelCall(_)) {
+                continue;
+            }
+            let exports = self
+                .header_args_with(node, &properties)
+                .and_then(|args| args.exports());
+            // same as the default `org-babel-default-header-args`
+            let (code, results) = match exports.unwrap_or(Exports::Code) {
+                Exports::Code => (true, false),
+                Exports::Results => (false, true),
+                Exports::None => (false, false),
+                Exports::Both => continue,
+            };
+            if !code {
+                hidden.insert(node);
+            }
+            if !results {
+                hidden.extend(self.results_with(node, |name| named.get(name).copied()));
+            }
+        }
+
+        hidden
+    }
+
     /// Writes an `Org` struct as org format.
     pub fn write_org<W>(&self, writer: W) -> Result<(), Error>
     where
         W: Write,
     {
-        self.write_org_custom(writer, &mut DefaultOrgHandler)
+        self.write_org_custom(writer, &mut DefaultOrgHandler::default())
     }
 
     /// Writes an `Org` struct as org format with custom `OrgHandler`.
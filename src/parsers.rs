@@ -1,17 +1,26 @@
+use std::borrow::Cow;
 use std::iter::once;
 use std::marker::PhantomData;
 
 use indextree::{Arena, NodeId};
 use jetscii::{bytes, BytesConst};
-use memchr::{memchr, memchr_iter};
+use memchr::{memchr, memchr_iter, memrchr};
 use nom::bytes::complete::take_while1;
 
 use crate::config::ParseConfig;
 use crate::elements::{
-    block::RawBlock, emphasis::Emphasis, keyword::RawKeyword, radio_target::parse_radio_target,
-    Clock, Comment, Cookie, Drawer, DynBlock, Element, FixedWidth, FnDef, FnRef, InlineCall,
-    InlineSrc, Link, List, ListItem, Macros, Rule, Snippet, Table, TableCell, TableRow, Target,
-    Timestamp, Title,
+    block::RawBlock,
+    emphasis::Emphasis,
+    keyword::{parse_affiliated_keywords, RawKeyword},
+    line_break::parse_line_break,
+    radio_target::find_radio_link,
+    script::Script,
+    table::parse_special_row,
+    title::parse_properties_drawer,
+    AffiliatedKeywords, Citation, Clock, Comment, Cookie, DiarySexp, Drawer, DynBlock, Element,
+    Entity, FixedWidth, FnDef, FnRef, InlineCall, InlineSrc, Inlinetask, LatexEnvironment,
+    LatexFragment, Link, List, ListItem, Macros, PropertiesMap, RadioLink, RadioTarget, Rule,
+    Snippet, Table, TableCell, TableRow, Target, Timestamp, Title,
 };
 use crate::parse::combinators::lines_while;
 
@@ -120,16 +129,16 @@ pub fn parse_container<'a, T: ElementArena<'a>>(
     while let Some(container) = containers.pop() {
         match container {
             Container::Document { content, node } => {
-                parse_section_and_headlines(arena, content, node, containers);
+                parse_section_and_headlines(arena, content, node, containers, config);
             }
             Container::Headline { content, node } => {
                 parse_headline_content(arena, content, node, containers, config);
             }
             Container::Block { content, node } => {
-                parse_blocks(arena, content, node, containers);
+                parse_blocks(arena, content, node, containers, config);
             }
             Container::Inline { content, node } => {
-                parse_inlines(arena, content, node, containers);
+                parse_inlines(arena, content, node, containers, config);
             }
         }
     }
@@ -145,7 +154,7 @@ pub fn parse_headline_content<'a, T: ElementArena<'a>>(
     let (tail, (title, content)) = Title::parse(content, config).unwrap();
     let node = arena.append(title, parent);
     containers.push(Container::Inline { content, node });
-    parse_section_and_headlines(arena, tail, parent, containers);
+    parse_section_and_headlines(arena, tail, parent, containers, config);
 }
 
 pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
@@ -153,6 +162,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
     let content = blank_lines_count(content).0;
 
@@ -162,7 +172,9 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
 
     let mut last_end = 0;
     for i in memchr_iter(b'\n', content.as_bytes()).chain(once(content.len())) {
-        if let Some((mut tail, (headline_content, level))) = parse_headline(&content[last_end..]) {
+        if let Some((mut tail, (headline_content, level))) =
+            parse_headline(&content[last_end..], config)
+        {
             if last_end != 0 {
                 let node = arena.append(Element::Section, parent);
                 let content = &content[0..last_end];
@@ -175,7 +187,7 @@ pub fn parse_section_and_headlines<'a, T: ElementArena<'a>>(
                 node,
             });
 
-            while let Some((new_tail, (content, level))) = parse_headline(tail) {
+            while let Some((new_tail, (content, level))) = parse_headline(tail, config) {
                 debug_assert_ne!(tail, new_tail);
                 let node = arena.append(Element::Headline { level }, parent);
                 containers.push(Container::Headline { content, node });
@@ -195,15 +207,14 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
     let mut tail = blank_lines_count(content).0;
 
-    if let Some(new_tail) = parse_block(content, arena, parent, containers) {
-        tail = blank_lines_count(new_tail).0;
-    }
-
     let mut text = tail;
     let mut pos = 0;
+    // affiliated keywords of the paragraph starting at `text`
+    let mut affiliated = None;
 
     while !tail.is_empty() {
         let i = memchr(b'\n', tail.as_bytes())
@@ -218,6 +229,7 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
                 Element::Paragraph {
                     // including the current line (&tail[0..i])
                     post_blank: blank + 1,
+                    affiliated: affiliated.take(),
                 },
                 parent,
             );
@@ -229,10 +241,59 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
 
             pos = 0;
             text = tail;
-        } else if let Some(new_tail) = parse_block(tail, arena, parent, containers) {
+        } else if let Some((new_tail, keywords)) = parse_affiliated_keywords(tail) {
+            if pos != 0 {
+                let node = arena.append(
+                    Element::Paragraph {
+                        post_blank: 0,
+                        affiliated: affiliated.take(),
+                    },
+                    parent,
+                );
+
+                containers.push(Container::Inline {
+                    content: text[0..pos].trim_end(),
+                    node,
+                });
+
+                pos = 0;
+            }
+
+            let mut attached = Some(Box::new(AffiliatedKeywords::from_raw(&keywords)));
+
+            if let Some(new_tail) =
+                parse_block(new_tail, arena, parent, containers, config, &mut attached)
+            {
+                if attached.is_some() {
+                    // element doesn't accept affiliated keywords
+                    for keyword in keywords {
+                        arena.insert_before_last_child(keyword.into_element(), parent);
+                    }
+                }
+                debug_assert_ne!(tail, blank_lines_count(new_tail).0);
+                tail = blank_lines_count(new_tail).0;
+            } else if new_tail.is_empty() {
+                // no element follows
+                for keyword in keywords {
+                    arena.append(keyword.into_element(), parent);
+                }
+                tail = new_tail;
+            } else {
+                affiliated = attached;
+                tail = new_tail;
+            }
+            text = tail;
+        } else if let Some(new_tail) =
+            parse_block(tail, arena, parent, containers, config, &mut None)
+        {
             if pos != 0 {
-                let node =
-                    arena.insert_before_last_child(Element::Paragraph { post_blank: 0 }, parent);
+                let node = arena.insert_before_last_child(
+                    Element::Paragraph {
+                        post_blank: 0,
+                        affiliated: affiliated.take(),
+                    },
+                    parent,
+                );
 
                 containers.push(Container::Inline {
                     content: &text[0..pos].trim_end(),
@@ -252,7 +313,13 @@ pub fn parse_blocks<'a, T: ElementArena<'a>>(
     }
 
     if !text.is_empty() {
-        let node = arena.append(Element::Paragraph { post_blank: 0 }, parent);
+        let node = arena.append(
+            Element::Paragraph {
+                post_blank: 0,
+                affiliated,
+            },
+            parent,
+        );
 
         containers.push(Container::Inline {
             content: &text[0..pos].trim_end(),
@@ -266,6 +333,8 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
     arena: &mut T,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
+    affiliated: &mut Option<Box<AffiliatedKeywords<'a>>>,
 ) -> Option<&'a str> {
     match contents
         .as_bytes()
@@ -274,12 +343,20 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
     {
         b'[' => {
             let (tail, (fn_def, content)) = FnDef::parse(contents)?;
-            let node = arena.append(fn_def, parent);
+            let node = arena.append(with_affiliated(fn_def, affiliated), parent);
             containers.push(Container::Block { content, node });
             Some(tail)
         }
-        b'0'..=b'9' | b'*' => {
-            let tail = parse_list(arena, contents, parent, containers)?;
+        b'*' => {
+            if let Some(tail) = parse_inlinetask(arena, contents, parent, containers, config) {
+                Some(tail)
+            } else {
+                let tail = parse_list(arena, contents, parent, containers, config, affiliated)?;
+                Some(tail)
+            }
+        }
+        b'0'..=b'9' => {
+            let tail = parse_list(arena, contents, parent, containers, config, affiliated)?;
             Some(tail)
         }
         b'C' => {
@@ -287,40 +364,46 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
             arena.append(clock, parent);
             Some(tail)
         }
-        b'\'' => {
-            // TODO: LaTeX environment
-            None
+        b'\\' => {
+            let (tail, env) = LatexEnvironment::parse(contents)?;
+            arena.append(with_affiliated(env, affiliated), parent);
+            Some(tail)
         }
         b'-' => {
             if let Some((tail, rule)) = Rule::parse(contents) {
-                arena.append(rule, parent);
+                arena.append(with_affiliated(rule, affiliated), parent);
                 Some(tail)
             } else {
-                let tail = parse_list(arena, contents, parent, containers)?;
+                let tail = parse_list(arena, contents, parent, containers, config, affiliated)?;
                 Some(tail)
             }
         }
         b':' => {
             if let Some((tail, (drawer, content))) = Drawer::parse(contents) {
-                let node = arena.append(drawer, parent);
+                let node = arena.append(with_affiliated(drawer, affiliated), parent);
                 containers.push(Container::Block { content, node });
                 Some(tail)
             } else {
                 let (tail, fixed_width) = FixedWidth::parse(contents)?;
-                arena.append(fixed_width, parent);
+                arena.append(with_affiliated(fixed_width, affiliated), parent);
                 Some(tail)
             }
         }
+        b'%' => {
+            let (tail, sexp) = DiarySexp::parse(contents)?;
+            arena.append(with_affiliated(sexp, affiliated), parent);
+            Some(tail)
+        }
         b'|' => {
-            let tail = parse_org_table(arena, contents, containers, parent);
+            let tail = parse_org_table(arena, contents, containers, parent, affiliated);
             Some(tail)
         }
         b'+' => {
             if let Some((tail, table)) = Table::parse_table_el(contents) {
-                arena.append(table, parent);
+                arena.append(with_affiliated(table, affiliated), parent);
                 Some(tail)
             } else {
-                let tail = parse_list(arena, contents, parent, containers)?;
+                let tail = parse_list(arena, contents, parent, containers, config, affiliated)?;
                 Some(tail)
             }
         }
@@ -335,17 +418,17 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
                     | Element::SpecialBlock(_) => true,
                     _ => false,
                 };
-                let node = arena.append(element, parent);
+                let node = arena.append(with_affiliated(element, affiliated), parent);
                 if is_block_container {
                     containers.push(Container::Block { content, node });
                 }
                 Some(tail)
             } else if let Some((tail, (dyn_block, content))) = DynBlock::parse(contents) {
-                let node = arena.append(dyn_block, parent);
+                let node = arena.append(with_affiliated(dyn_block, affiliated), parent);
                 containers.push(Container::Block { content, node });
                 Some(tail)
             } else if let Some((tail, keyword)) = RawKeyword::parse(contents) {
-                arena.append(keyword.into_element(), parent);
+                arena.append(with_affiliated(keyword.into_element(), affiliated), parent);
                 Some(tail)
             } else {
                 let (tail, comment) = Comment::parse(contents)?;
@@ -357,6 +440,17 @@ pub fn parse_block<'a, T: ElementArena<'a>>(
     }
 }
 
+fn with_affiliated<'a, E: Into<Element<'a>>>(
+    element: E,
+    affiliated: &mut Option<Box<AffiliatedKeywords<'a>>>,
+) -> Element<'a> {
+    let mut element = element.into();
+    if let Some(slot) = element.affiliated_mut() {
+        *slot = affiliated.take();
+    }
+    element
+}
+
 struct InlinePositions<'a> {
     bytes: &'a [u8],
     pos: usize,
@@ -379,7 +473,9 @@ impl Iterator for InlinePositions<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         lazy_static::lazy_static! {
             static ref PRE_BYTES: BytesConst =
-                bytes!(b'@', b'<', b'[', b' ', b'(', b'{', b'\'', b'"', b'\n');
+                bytes!(
+                b'@', b'<', b'[', b' ', b'(', b'{', b'\'', b'"', b'\n', b'\\', b'$', b'_', b'^'
+            );
         }
 
         self.next.take().or_else(|| {
@@ -404,15 +500,18 @@ pub fn parse_inlines<'a, T: ElementArena<'a>>(
     content: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
 ) {
     let mut tail = content;
 
-    if let Some(tail_) = parse_inline(tail, arena, containers, parent) {
+    if let Some(tail_) = parse_inline(tail, arena, containers, parent, config) {
         tail = tail_;
     }
 
     while let Some((tail_, i)) = InlinePositions::new(tail.as_bytes())
-        .filter_map(|i| parse_inline(&tail[i..], arena, containers, parent).map(|tail| (tail, i)))
+        .filter_map(|i| {
+            parse_inline_at(tail, i, arena, containers, parent, config).map(|tail| (tail, i))
+        })
         .next()
     {
         if i != 0 {
@@ -431,11 +530,55 @@ pub fn parse_inlines<'a, T: ElementArena<'a>>(
     }
 }
 
+// parses an inline element at `text[pos..]`
+fn parse_inline_at<'a, T: ElementArena<'a>>(
+    text: &'a str,
+    pos: usize,
+    arena: &mut T,
+    containers: &mut Vec<Container<'a>>,
+    parent: NodeId,
+    config: &ParseConfig,
+) -> Option<&'a str> {
+    let contents = &text[pos..];
+    let pre = text[0..pos].chars().last();
+
+    // line break must not follow a backslash
+    if contents.starts_with("\\\\") && pre != Some('\\') {
+        if let Some(tail) = parse_line_break(contents) {
+            arena.append(Element::LineBreak, parent);
+            return Some(tail);
+        }
+    }
+
+    if !contents.starts_with(&['_', '^'][..]) {
+        return parse_inline(contents, arena, containers, parent, config);
+    }
+
+    // subscript and superscript must follow a non-whitespace character
+    if matches!(pre, Some(c) if !c.is_whitespace()) {
+        if let Some((tail, script)) = Script::parse(contents, config.use_sub_superscripts) {
+            let (element, content) = script.into_element();
+            let node = arena.append(element, parent);
+            containers.push(Container::Inline { content, node });
+            return Some(tail);
+        }
+    }
+
+    // otherwise only emphasis is allowed after its pre characters
+    match pre {
+        None | Some(' ') | Some('(') | Some('{') | Some('\'') | Some('"') | Some('\n') => {
+            parse_inline(contents, arena, containers, parent, config)
+        }
+        _ => None,
+    }
+}
+
 pub fn parse_inline<'a, T: ElementArena<'a>>(
     contents: &'a str,
     arena: &mut T,
     containers: &mut Vec<Container<'a>>,
     parent: NodeId,
+    config: &ParseConfig,
 ) -> Option<&'a str> {
     if contents.len() < 3 {
         return None;
@@ -455,12 +598,15 @@ pub fn parse_inline<'a, T: ElementArena<'a>>(
             Some(tail)
         }
         b'<' => {
-            if let Some((tail, _content)) = parse_radio_target(contents) {
-                arena.append(Element::RadioTarget, parent);
+            if let Some((tail, radio)) = RadioTarget::parse(contents) {
+                arena.append(radio, parent);
                 Some(tail)
             } else if let Some((tail, target)) = Target::parse(contents) {
                 arena.append(target, parent);
                 Some(tail)
+            } else if let Some((tail, link)) = Link::parse_angle(contents, &config.link_types) {
+                arena.append(link, parent);
+                Some(tail)
             } else if let Some((tail, timestamp)) = Timestamp::parse_active(contents) {
                 arena.append(timestamp, parent);
                 Some(tail)
@@ -474,6 +620,9 @@ pub fn parse_inline<'a, T: ElementArena<'a>>(
             if let Some((tail, fn_ref)) = FnRef::parse(contents) {
                 arena.append(fn_ref, parent);
                 Some(tail)
+            } else if let Some((tail, citation)) = Citation::parse(contents) {
+                arena.append(citation, parent);
+                Some(tail)
             } else if let Some((tail, link)) = Link::parse(contents) {
                 arena.append(link, parent);
                 Some(tail)
@@ -499,16 +648,36 @@ pub fn parse_inline<'a, T: ElementArena<'a>>(
             }
             Some(tail)
         }
-        b's' => {
+        b'\\' => {
+            if let Some((tail, entity)) = Entity::parse(contents) {
+                arena.append(entity, parent);
+                Some(tail)
+            } else {
+                let (tail, fragment) = LatexFragment::parse(contents)?;
+                arena.append(fragment, parent);
+                Some(tail)
+            }
+        }
+        b'$' => {
+            let (tail, fragment) = LatexFragment::parse(contents)?;
+            arena.append(fragment, parent);
+            Some(tail)
+        }
+        b's' if contents.starts_with("src_") => {
             let (tail, inline_src) = InlineSrc::parse(contents)?;
             arena.append(inline_src, parent);
             Some(tail)
         }
-        b'c' => {
+        b'c' if contents.starts_with("call_") => {
             let (tail, inline_call) = InlineCall::parse(contents)?;
             arena.append(inline_call, parent);
             Some(tail)
         }
+        _ if byte.is_ascii_alphanumeric() => {
+            let (tail, link) = Link::parse_plain(contents, &config.link_types)?;
+            arena.append(link, parent);
+            Some(tail)
+        }
         _ => None,
     }
 }
@@ -518,20 +687,30 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
     contents: &'a str,
     parent: NodeId,
     containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
+    affiliated: &mut Option<Box<AffiliatedKeywords<'a>>>,
 ) -> Option<&'a str> {
-    let (mut tail, (first_item, content)) = ListItem::parse(contents)?;
+    let (mut tail, (first_item, mut content)) = ListItem::parse(contents, config.tab_width)?;
     let first_item_indent = first_item.indent;
     let first_item_ordered = first_item.ordered;
+    let first_item_description = !first_item.ordered && first_item.tag.is_some();
 
-    let parent = arena.append(Element::Document { pre_blank: 0 }, parent); // placeholder
+    let parent = arena.append(
+        Element::Document {
+            pre_blank: 0,
+            properties_pre_blank: 0,
+            properties: PropertiesMap::new(),
+        },
+        parent,
+    ); // placeholder
 
-    let node = arena.append(first_item, parent);
-    containers.push(Container::Block { content, node });
+    let mut node = arena.append(first_item, parent);
 
-    while let Some((tail_, (item, content))) = ListItem::parse(tail) {
+    while let Some((tail_, (item, content_))) = ListItem::parse(tail, config.tab_width) {
         if item.indent == first_item_indent {
-            let node = arena.append(item, parent);
             containers.push(Container::Block { content, node });
+            node = arena.append(item, parent);
+            content = content_;
             debug_assert_ne!(tail, tail_);
             tail = tail_;
         } else {
@@ -539,6 +718,10 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
         }
     }
 
+    // blank lines after the last item belong to the list
+    let (content, trailing_blank) = blank_lines_count_end(content);
+    containers.push(Container::Block { content, node });
+
     let (tail, post_blank) = blank_lines_count(tail);
 
     arena.set(
@@ -546,53 +729,94 @@ pub fn parse_list<'a, T: ElementArena<'a>>(
         List {
             indent: first_item_indent,
             ordered: first_item_ordered,
-            post_blank,
+            description: first_item_description,
+            post_blank: trailing_blank + post_blank,
+            affiliated: affiliated.take(),
         },
     );
 
     Some(tail)
 }
 
+pub fn parse_inlinetask<'a, T: ElementArena<'a>>(
+    arena: &mut T,
+    contents: &'a str,
+    parent: NodeId,
+    containers: &mut Vec<Container<'a>>,
+    config: &ParseConfig,
+) -> Option<&'a str> {
+    let (tail, (inlinetask, content)) = Inlinetask::parse(contents, config.inlinetask_min_level?)?;
+    let (content, (title, title_content)) = Title::parse(content, config)?;
+
+    let parent = arena.append(inlinetask, parent);
+    let node = arena.append(title, parent);
+    containers.push(Container::Inline {
+        content: title_content,
+        node,
+    });
+    containers.push(Container::Block {
+        content,
+        node: parent,
+    });
+
+    Some(tail)
+}
+
 pub fn parse_org_table<'a, T: ElementArena<'a>>(
     arena: &mut T,
     contents: &'a str,
     containers: &mut Vec<Container<'a>>,
     parent: NodeId,
+    affiliated: &mut Option<Box<AffiliatedKeywords<'a>>>,
 ) -> &'a str {
     let (tail, contents) =
         lines_while(|line| line.trim_start().starts_with('|'))(contents).unwrap_or((contents, ""));
+    let (tail, tblfm) = lines_while(
+        |line| matches!(line.trim_start().get(0..8), Some(s) if s.eq_ignore_ascii_case("#+TBLFM:")),
+    )(tail)
+    .unwrap_or((tail, ""));
     let (tail, post_blank) = blank_lines_count(tail);
 
-    let mut iter = contents.trim_end().lines().peekable();
+    let tblfm = tblfm
+        .lines()
+        .map(|line| line.trim()[8..].trim().into())
+        .collect();
 
+    let mut columns = vec![];
+    let mut special_rows = vec![];
     let mut lines = vec![];
 
-    let mut has_header = false;
+    for line in contents.trim_end().lines() {
+        if parse_special_row(line.trim(), &mut columns) {
+            special_rows.push((lines.len(), line.trim().into()));
+        } else {
+            lines.push(line.trim_start());
+        }
+    }
 
     // TODO: merge contiguous rules
 
-    if let Some(line) = iter.next() {
-        let line = line.trim_start();
-        if !line.starts_with("|-") {
-            lines.push(line);
-        }
+    // leading and trailing rules are dropped
+    if lines.len() > 1 && matches!(lines.last(), Some(line) if line.starts_with("|-")) {
+        lines.pop();
     }
-
-    while let Some(line) = iter.next() {
-        let line = line.trim_start();
-        if iter.peek().is_none() && line.starts_with("|-") {
-            break;
-        } else if line.starts_with("|-") {
-            has_header = true;
+    if matches!(lines.first(), Some(line) if line.starts_with("|-")) {
+        lines.remove(0);
+        for (before, _) in &mut special_rows {
+            *before = before.saturating_sub(1);
         }
-        lines.push(line);
     }
 
+    let mut has_header = lines.iter().any(|line| line.starts_with("|-"));
+
     let parent = arena.append(
         Table::Org {
-            tblfm: None,
+            tblfm,
             post_blank,
             has_header,
+            columns,
+            special_rows,
+            affiliated: affiliated.take(),
         },
         parent,
     );
@@ -631,12 +855,112 @@ pub fn parse_org_table<'a, T: ElementArena<'a>>(
     tail
 }
 
+/// Splits radio links out of text nodes under `root`, after the whole
+/// document has been parsed.
+pub fn parse_radio_links<'a>(arena: &mut Arena<Element<'a>>, root: NodeId) {
+    let targets: Vec<Cow<'a, str>> = root
+        .descendants(arena)
+        .filter_map(|node| match arena[node].get() {
+            Element::RadioTarget(RadioTarget { target }) => Some(target.clone()),
+            _ => None,
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return;
+    }
+
+    let texts: Vec<NodeId> = root
+        .descendants(arena)
+        .filter(|&node| matches!(arena[node].get(), Element::Text { .. }))
+        .collect();
+
+    for node in texts {
+        let value = match arena[node].get() {
+            Element::Text { value } => value.clone(),
+            _ => unreachable!(),
+        };
+
+        let mut pos = 0;
+        while let Some((start, end, idx)) = find_radio_link(&value[pos..], &targets) {
+            let (start, end) = (pos + start, pos + end);
+            if start > pos {
+                let text = arena.new_node(Element::Text {
+                    value: slice(&value, pos, start),
+                });
+                node.insert_before(text, arena);
+            }
+            let link = arena.new_node(Element::RadioLink(RadioLink {
+                target: targets[idx].clone(),
+                value: slice(&value, start, end),
+            }));
+            node.insert_before(link, arena);
+            pos = end;
+        }
+
+        if pos == 0 {
+            continue;
+        } else if pos < value.len() {
+            *arena[node].get_mut() = Element::Text {
+                value: slice(&value, pos, value.len()),
+            };
+        } else {
+            node.remove(arena);
+        }
+    }
+}
+
+fn slice<'a>(value: &Cow<'a, str>, start: usize, end: usize) -> Cow<'a, str> {
+    match value {
+        Cow::Borrowed(value) => Cow::Borrowed(&value[start..end]),
+        Cow::Owned(value) => Cow::Owned(value[start..end].to_string()),
+    }
+}
+
+/// Parses the property drawer at the beginning of document, returns the
+/// numbers of blank lines before it, its properties and the numbers of
+/// blank lines before document contents.
+pub fn parse_document_properties(text: &str) -> (&str, (usize, PropertiesMap<'_>, usize)) {
+    let (tail, blank) = blank_lines_count(text);
+    match parse_properties_drawer(tail) {
+        Ok((tail, properties)) => {
+            let (tail, pre_blank) = blank_lines_count(tail);
+            (tail, (blank, properties, pre_blank))
+        }
+        Err(_) => (tail, (0, PropertiesMap::new(), blank)),
+    }
+}
+
 pub fn blank_lines_count(input: &str) -> (&str, usize) {
     crate::parse::combinators::blank_lines_count(input).unwrap_or((input, 0))
 }
 
-pub fn parse_headline(input: &str) -> Option<(&str, (&str, usize))> {
-    let (input_, level) = parse_headline_level(input)?;
+// counts blank lines at the end of input, returns input without them
+pub fn blank_lines_count_end(input: &str) -> (&str, usize) {
+    let mut input = input;
+    let mut count = 0;
+
+    while !input.is_empty() {
+        let start = memrchr(b'\n', &input.as_bytes()[0..input.len() - 1])
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if !input[start..].chars().all(char::is_whitespace) {
+            break;
+        }
+        count += 1;
+        input = &input[0..start];
+    }
+
+    (input, count)
+}
+
+pub fn parse_headline<'a>(
+    input: &'a str,
+    config: &ParseConfig,
+) -> Option<(&'a str, (&'a str, usize))> {
+    // deeper headlines are inlinetasks
+    let min_level = config.inlinetask_min_level.unwrap_or(usize::MAX);
+    let (input_, level) = parse_headline_level(input).filter(|(_, l)| *l < min_level)?;
     let (input_, content) = lines_while(move |line| {
         parse_headline_level(line)
             .map(|(_, l)| l > level)
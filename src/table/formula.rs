@@ -0,0 +1,712 @@
+//! Table formulas
+//!
+//! Evaluates a subset of Org's formula syntax, see `OrgTable::evaluate`.
+
+use std::collections::HashMap;
+
+/// Table Formula Error
+#[derive(Debug)]
+pub enum FormulaError {
+    /// Formula can't be parsed or uses unsupported syntax
+    Syntax { formula: String },
+    /// Reference points outside of the table
+    InvalidReference { formula: String },
+    /// Function is not supported
+    UnknownFunction { formula: String, name: String },
+}
+
+/// Table values, excluding horizontal rules
+pub(crate) struct Grid {
+    pub rows: Vec<Vec<String>>,
+    /// Numbers of data rows before each horizontal rule
+    pub hlines: Vec<usize>,
+    /// Numbers of data rows in table header
+    pub header_rows: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Row {
+    Current,
+    Abs(usize),
+    Rel(isize),
+    First(usize),
+    Last(usize),
+    Hline(usize, isize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Column {
+    Current,
+    Abs(usize),
+    Rel(isize),
+    First(usize),
+    Last(usize),
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Ref {
+    row: Option<Row>,
+    column: Option<Column>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Num(f64),
+    Ref(Ref),
+    Range(Ref, Ref),
+    Neg(Box<Expr>),
+    Binary(u8, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+enum Value {
+    Num(f64),
+    List(Vec<f64>),
+}
+
+struct Formula<'f> {
+    raw: &'f str,
+    target: Ref,
+    expr: Expr,
+    decimals: Option<usize>,
+}
+
+/// Evaluates `formulas`, i.e. lines of `#+TBLFM`, and stores results in
+/// `grid`. Column formulas are evaluated before field formulas.
+pub(crate) fn evaluate<S: AsRef<str>>(grid: &mut Grid, tblfm: &[S]) -> Result<(), FormulaError> {
+    let formulas = tblfm
+        .iter()
+        .flat_map(|line| line.as_ref().split("::"))
+        .map(str::trim)
+        .filter(|formula| !formula.is_empty())
+        .map(parse_formula)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let names = column_names(grid);
+
+    let (columns, fields): (Vec<_>, Vec<_>) = formulas
+        .into_iter()
+        .partition(|formula| formula.target.row.is_none());
+
+    for formula in columns {
+        let invalid = || FormulaError::InvalidReference {
+            formula: formula.raw.into(),
+        };
+        for row in grid.header_rows..grid.rows.len() {
+            if is_special_row(&grid.rows[row]) {
+                continue;
+            }
+            let column = resolve_column(grid, &names, &formula.target, 0).ok_or_else(invalid)?;
+            let ctx = Context {
+                grid,
+                names: &names,
+                row,
+                column,
+                formula: formula.raw,
+            };
+            let value = ctx.eval_number(&formula.expr)?;
+            set(grid, row, column, format_number(value, formula.decimals));
+        }
+    }
+
+    for formula in fields {
+        let invalid = || FormulaError::InvalidReference {
+            formula: formula.raw.into(),
+        };
+        let row = resolve_row(grid, &formula.target, 0, false).ok_or_else(invalid)?;
+        let column = resolve_column(grid, &names, &formula.target, 0).ok_or_else(invalid)?;
+        let ctx = Context {
+            grid,
+            names: &names,
+            row,
+            column,
+            formula: formula.raw,
+        };
+        let value = ctx.eval_number(&formula.expr)?;
+        set(grid, row, column, format_number(value, formula.decimals));
+    }
+
+    Ok(())
+}
+
+fn set(grid: &mut Grid, row: usize, column: usize, value: String) {
+    let cells = &mut grid.rows[row];
+    if cells.len() <= column {
+        cells.resize(column + 1, String::new());
+    }
+    cells[column] = value;
+}
+
+fn is_special_row(cells: &[String]) -> bool {
+    matches!(
+        cells.first().map(|cell| cell.trim()),
+        Some("!") | Some("^") | Some("_") | Some("$") | Some("/")
+    )
+}
+
+fn column_names(grid: &Grid) -> HashMap<String, usize> {
+    let mut names = HashMap::new();
+    for cells in &grid.rows {
+        if cells.first().map(|cell| cell.trim()) == Some("!") {
+            for (i, name) in cells.iter().enumerate().skip(1) {
+                if !name.trim().is_empty() {
+                    names.insert(name.trim().to_string(), i);
+                }
+            }
+        }
+    }
+    names
+}
+
+fn columns_count(grid: &Grid) -> usize {
+    grid.rows.iter().map(Vec::len).max().unwrap_or_default()
+}
+
+// resolves row of `reference` to an index of data rows, `end` indicates
+// whether it's the end of a range
+fn resolve_row(grid: &Grid, reference: &Ref, current: usize, end: bool) -> Option<usize> {
+    let len = grid.rows.len();
+    let row = match reference.row.unwrap_or(Row::Current) {
+        Row::Current => Some(current),
+        Row::Abs(n) => n.checked_sub(1),
+        Row::Rel(n) => offset(current, n),
+        Row::First(n) => n.checked_sub(1),
+        Row::Last(n) => len.checked_sub(n),
+        Row::Hline(i, n) => {
+            let before = *grid.hlines.get(i.checked_sub(1)?)?;
+            match n {
+                0 if end => before.checked_sub(1),
+                0 => Some(before),
+                n if n > 0 => offset(before, n - 1),
+                n => offset(before, n),
+            }
+        }
+    }?;
+    if row < len {
+        Some(row)
+    } else {
+        None
+    }
+}
+
+fn resolve_column(
+    grid: &Grid,
+    names: &HashMap<String, usize>,
+    reference: &Ref,
+    current: usize,
+) -> Option<usize> {
+    let len = columns_count(grid);
+    let column = match reference.column.as_ref().unwrap_or(&Column::Current) {
+        Column::Current => Some(current),
+        Column::Abs(n) => n.checked_sub(1),
+        Column::Rel(n) => offset(current, *n),
+        Column::First(n) => n.checked_sub(1),
+        Column::Last(n) => len.checked_sub(*n),
+        Column::Name(name) => names.get(name).copied(),
+    }?;
+    if column < len {
+        Some(column)
+    } else {
+        None
+    }
+}
+
+fn offset(base: usize, n: isize) -> Option<usize> {
+    if n < 0 {
+        base.checked_sub(n.unsigned_abs())
+    } else {
+        base.checked_add(n as usize)
+    }
+}
+
+struct Context<'c> {
+    grid: &'c Grid,
+    names: &'c HashMap<String, usize>,
+    row: usize,
+    column: usize,
+    formula: &'c str,
+}
+
+impl Context<'_> {
+    fn invalid(&self) -> FormulaError {
+        FormulaError::InvalidReference {
+            formula: self.formula.into(),
+        }
+    }
+
+    fn syntax(&self) -> FormulaError {
+        FormulaError::Syntax {
+            formula: self.formula.into(),
+        }
+    }
+
+    fn field(&self, row: usize, column: usize) -> Option<f64> {
+        let value = self.grid.rows[row].get(column)?.trim();
+        value.parse().ok()
+    }
+
+    fn eval_number(&self, expr: &Expr) -> Result<f64, FormulaError> {
+        match self.eval(expr)? {
+            Value::Num(num) => Ok(num),
+            Value::List(_) => Err(self.syntax()),
+        }
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Value, FormulaError> {
+        match expr {
+            Expr::Num(num) => Ok(Value::Num(*num)),
+            Expr::Ref(reference) => {
+                let row = resolve_row(self.grid, reference, self.row, false)
+                    .ok_or_else(|| self.invalid())?;
+                let column = resolve_column(self.grid, self.names, reference, self.column)
+                    .ok_or_else(|| self.invalid())?;
+                Ok(Value::Num(self.field(row, column).unwrap_or_default()))
+            }
+            Expr::Range(start, end) => {
+                let rows = (
+                    resolve_row(self.grid, start, self.row, false),
+                    resolve_row(self.grid, end, self.row, true),
+                );
+                let columns = (
+                    resolve_column(self.grid, self.names, start, self.column),
+                    resolve_column(self.grid, self.names, end, self.column),
+                );
+                match (rows, columns) {
+                    ((Some(r1), Some(r2)), (Some(c1), Some(c2))) => {
+                        let mut values = vec![];
+                        for row in r1.min(r2)..=r1.max(r2) {
+                            for column in c1.min(c2)..=c1.max(c2) {
+                                values.extend(self.field(row, column));
+                            }
+                        }
+                        Ok(Value::List(values))
+                    }
+                    _ => Err(self.invalid()),
+                }
+            }
+            Expr::Neg(expr) => Ok(Value::Num(-self.eval_number(expr)?)),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (self.eval_number(lhs)?, self.eval_number(rhs)?);
+                Ok(Value::Num(match op {
+                    b'+' => lhs + rhs,
+                    b'-' => lhs - rhs,
+                    b'*' => lhs * rhs,
+                    b'/' => lhs / rhs,
+                    _ => lhs.powf(rhs),
+                }))
+            }
+            Expr::Call(name, args) => {
+                let mut values = vec![];
+                for arg in args {
+                    match self.eval(arg)? {
+                        Value::Num(num) => values.push(num),
+                        Value::List(list) => values.extend(list),
+                    }
+                }
+                self.call(name, values)
+            }
+        }
+    }
+
+    fn call(&self, name: &str, mut values: Vec<f64>) -> Result<Value, FormulaError> {
+        let first = values.first().copied();
+        let arg = || first.ok_or_else(|| self.syntax());
+
+        let value = match name {
+            "vsum" => values.iter().sum(),
+            "vprod" => values.iter().product(),
+            "vcount" => values.len() as f64,
+            "vmean" if values.is_empty() => f64::NAN,
+            "vmean" => values.iter().sum::<f64>() / values.len() as f64,
+            "vmin" | "min" => values.iter().copied().fold(f64::NAN, f64::min),
+            "vmax" | "max" => values.iter().copied().fold(f64::NAN, f64::max),
+            "vmedian" if values.is_empty() => f64::NAN,
+            "vmedian" => {
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                let len = values.len();
+                (values[(len - 1) / 2] + values[len / 2]) / 2.
+            }
+            "abs" => arg()?.abs(),
+            "sqrt" => arg()?.sqrt(),
+            "exp" => arg()?.exp(),
+            "ln" => arg()?.ln(),
+            "log10" => arg()?.log10(),
+            "floor" => arg()?.floor(),
+            "ceil" => arg()?.ceil(),
+            "round" => {
+                let digits = values.get(1).copied().unwrap_or_default();
+                let scale = 10f64.powi(digits as i32);
+                (arg()? * scale).round() / scale
+            }
+            _ => {
+                return Err(FormulaError::UnknownFunction {
+                    formula: self.formula.into(),
+                    name: name.into(),
+                })
+            }
+        };
+
+        Ok(Value::Num(value))
+    }
+}
+
+fn format_number(value: f64, decimals: Option<usize>) -> String {
+    if !value.is_finite() {
+        return "#ERROR".into();
+    }
+
+    if let Some(decimals) = decimals {
+        return format!("{:.*}", decimals, value);
+    }
+
+    if value.fract() == 0. && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+
+    // keeps 12 significant digits like calc
+    let digits = 12 - (value.abs().log10().floor() as i32 + 1);
+    let value = format!("{:.*}", digits.max(0) as usize, value);
+    value.trim_end_matches('0').trim_end_matches('.').into()
+}
+
+fn parse_formula(raw: &str) -> Result<Formula<'_>, FormulaError> {
+    let syntax = || FormulaError::Syntax {
+        formula: raw.into(),
+    };
+
+    let (formula, format) = match raw.find(';') {
+        Some(i) => (&raw[0..i], &raw[i + 1..]),
+        None => (raw, ""),
+    };
+
+    let decimals = format
+        .find("%.")
+        .map(|i| &format[i + 2..])
+        .and_then(|format| {
+            format
+                .strip_suffix('f')
+                .or_else(|| format.split('f').next())
+        })
+        .and_then(|digits| digits.parse().ok());
+
+    let eq = formula.find('=').ok_or_else(syntax)?;
+    let (lhs, rhs) = (formula[0..eq].trim(), formula[eq + 1..].trim());
+
+    let mut parser = Parser { input: lhs, pos: 0 };
+    let target = parser.reference().ok_or_else(syntax)?;
+    if !parser.eof() || target.column.is_none() {
+        return Err(syntax());
+    }
+
+    let mut parser = Parser { input: rhs, pos: 0 };
+    let expr = parser.expr().ok_or_else(syntax)?;
+    if !parser.eof() {
+        return Err(syntax());
+    }
+
+    Ok(Formula {
+        raw,
+        target,
+        expr,
+        decimals,
+    })
+}
+
+struct Parser<'p> {
+    input: &'p str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_spaces(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_spaces();
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        self.skip_spaces();
+        if self.input[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eof(&mut self) -> bool {
+        self.peek().is_none()
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &str {
+        let start = self.pos;
+        while self
+            .input
+            .as_bytes()
+            .get(self.pos)
+            .is_some_and(|&c| predicate(c))
+        {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn count(&mut self, c: u8) -> usize {
+        self.take_while(|b| b == c).len()
+    }
+
+    fn signed(&mut self) -> Option<(bool, isize)> {
+        let sign = match self.input.as_bytes().get(self.pos) {
+            Some(b'+') => Some(1),
+            Some(b'-') => Some(-1),
+            _ => None,
+        };
+        if sign.is_some() {
+            self.pos += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let num: isize = digits.parse().ok()?;
+        Some((sign.is_some(), num * sign.unwrap_or(1)))
+    }
+
+    fn expr(&mut self) -> Option<Expr> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(op @ b'+') | Some(op @ b'-') => op,
+                _ => return Some(lhs),
+            };
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Option<Expr> {
+        let mut lhs = self.factor()?;
+        loop {
+            let op = match self.peek() {
+                Some(op @ b'*') | Some(op @ b'/') => op,
+                _ => return Some(lhs),
+            };
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Option<Expr> {
+        let base = self.unary()?;
+        if self.eat("^") {
+            Some(Expr::Binary(b'^', Box::new(base), Box::new(self.factor()?)))
+        } else {
+            Some(base)
+        }
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        if self.eat("-") {
+            Some(Expr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Option<Expr> {
+        match self.peek()? {
+            b'(' => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                if self.eat(")") {
+                    Some(expr)
+                } else {
+                    None
+                }
+            }
+            b'@' | b'$' => {
+                let start = self.reference()?;
+                if self.eat("..") {
+                    Some(Expr::Range(start, self.reference()?))
+                } else {
+                    Some(Expr::Ref(start))
+                }
+            }
+            c if c.is_ascii_digit() || c == b'.' => {
+                let num = self.take_while(|c| c.is_ascii_digit() || c == b'.');
+                num.parse().ok().map(Expr::Num)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let name = self
+                    .take_while(|c| c.is_ascii_alphanumeric() || c == b'_')
+                    .to_string();
+                if !self.eat("(") {
+                    return None;
+                }
+                let mut args = vec![];
+                if !self.eat(")") {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(")") {
+                            break;
+                        } else if !self.eat(",") {
+                            return None;
+                        }
+                    }
+                }
+                Some(Expr::Call(name, args))
+            }
+            _ => None,
+        }
+    }
+
+    fn reference(&mut self) -> Option<Ref> {
+        self.skip_spaces();
+
+        let row = if self.eat("@") {
+            Some(self.row()?)
+        } else {
+            None
+        };
+
+        let column = if self.input[self.pos..].starts_with('$') {
+            self.pos += 1;
+            Some(self.column()?)
+        } else {
+            None
+        };
+
+        if row.is_none() && column.is_none() {
+            None
+        } else {
+            Some(Ref { row, column })
+        }
+    }
+
+    fn row(&mut self) -> Option<Row> {
+        match self.input.as_bytes().get(self.pos)? {
+            b'<' => Some(Row::First(self.count(b'<'))),
+            b'>' => Some(Row::Last(self.count(b'>'))),
+            b'#' => {
+                self.pos += 1;
+                Some(Row::Current)
+            }
+            b'I' => {
+                let hline = self.count(b'I');
+                let offset = match self.input.as_bytes().get(self.pos) {
+                    Some(b'+') | Some(b'-') => self.signed()?.1,
+                    _ => 0,
+                };
+                Some(Row::Hline(hline, offset))
+            }
+            _ => match self.signed()? {
+                (true, n) => Some(Row::Rel(n)),
+                (false, n) => Some(Row::Abs(n as usize)),
+            },
+        }
+    }
+
+    fn column(&mut self) -> Option<Column> {
+        match self.input.as_bytes().get(self.pos)? {
+            b'<' => Some(Column::First(self.count(b'<'))),
+            b'>' => Some(Column::Last(self.count(b'>'))),
+            b'#' => {
+                self.pos += 1;
+                Some(Column::Current)
+            }
+            c if c.is_ascii_alphabetic() || *c == b'_' => {
+                let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'_');
+                Some(Column::Name(name.into()))
+            }
+            _ => match self.signed()? {
+                (true, n) => Some(Column::Rel(n)),
+                (false, n) => Some(Column::Abs(n as usize)),
+            },
+        }
+    }
+}
+
+#[test]
+fn parse() {
+    let formula = parse_formula("@>$2=vsum(@2..@-1);%.2f").unwrap();
+    assert_eq!(
+        formula.target,
+        Ref {
+            row: Some(Row::Last(1)),
+            column: Some(Column::Abs(2)),
+        }
+    );
+    assert_eq!(
+        formula.expr,
+        Expr::Call(
+            "vsum".into(),
+            vec![Expr::Range(
+                Ref {
+                    row: Some(Row::Abs(2)),
+                    column: None,
+                },
+                Ref {
+                    row: Some(Row::Rel(-1)),
+                    column: None,
+                }
+            )]
+        )
+    );
+    assert_eq!(formula.decimals, Some(2));
+
+    let formula = parse_formula("$total = $price * -$qty ^ 2").unwrap();
+    assert_eq!(
+        formula.target,
+        Ref {
+            row: None,
+            column: Some(Column::Name("total".into())),
+        }
+    );
+
+    assert!(parse_formula("$3='(+ $1 $2)").is_err());
+    assert!(parse_formula("@2=1").is_err());
+    assert!(parse_formula("$3=$1 +").is_err());
+}
+
+#[test]
+fn evaluate_grid() {
+    let mut grid = Grid {
+        rows: vec![
+            vec!["item".into(), "price".into(), "qty".into(), "total".into()],
+            vec!["!".into(), "price".into(), "qty".into(), "total".into()],
+            vec!["a".into(), "1.5".into(), "2".into(), "".into()],
+            vec!["b".into(), "2".into(), "".into()],
+            vec!["sum".into(), "".into(), "".into(), "".into()],
+        ],
+        hlines: vec![1, 4],
+        header_rows: 1,
+    };
+
+    evaluate(
+        &mut grid,
+        &[
+            "$total=$price*$qty::@>$2=vsum(@I..@II)",
+            "@>$4=vmean(@3..@4)/3",
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(grid.rows[1][3], "total");
+    assert_eq!(grid.rows[2][3], "3");
+    assert_eq!(grid.rows[3][3], "0");
+    assert_eq!(grid.rows[4][1], "3.5");
+    assert_eq!(grid.rows[4][3], "0.5");
+
+    assert!(matches!(
+        evaluate(&mut grid, &["$5=$1"]),
+        Err(FormulaError::InvalidReference { .. })
+    ));
+    assert!(matches!(
+        evaluate(&mut grid, &["$4=foo($1)"]),
+        Err(FormulaError::UnknownFunction { .. })
+    ));
+    assert_eq!(format_number(1. / 3., None), "0.333333333333");
+    assert_eq!(format_number(2., Some(2)), "2.00");
+}
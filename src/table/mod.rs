@@ -0,0 +1,512 @@
+//! Org tables
+
+#[cfg(feature = "ser")]
+mod de;
+mod formula;
+
+pub use formula::FormulaError;
+
+use indextree::{NodeEdge, NodeId};
+use std::borrow::Cow;
+use std::io::{Error, Write};
+
+use crate::elements::{Element, Entity, Table, TableCell, TableRow};
+use crate::export::{DefaultOrgHandler, OrgHandler};
+use crate::Org;
+
+use formula::Grid;
+
+/// Represents an "org" type table in `Org` struct.
+///
+/// Each `OrgTable` is associated with a table element, while its rows and
+/// cells are retrieved from the arena.
+#[derive(Copy, Clone, Debug)]
+pub struct OrgTable {
+    tbl_n: NodeId,
+}
+
+impl OrgTable {
+    pub(crate) fn from_node(tbl_n: NodeId, org: &Org) -> Option<OrgTable> {
+        match org[tbl_n] {
+            Element::Table(Table::Org { .. }) => Some(OrgTable { tbl_n }),
+            _ => None,
+        }
+    }
+
+    /// Returns the ID of the table element of this table.
+    pub fn table_node(self) -> NodeId {
+        self.tbl_n
+    }
+
+    /// Returns the formulas from `#+TBLFM` lines of this table.
+    pub fn formulas<'a: 'b, 'b>(self, org: &'b Org<'a>) -> &'b [Cow<'a, str>] {
+        match &org[self.tbl_n] {
+            Element::Table(Table::Org { tblfm, .. }) => tblfm,
+            _ => unreachable!("OrgTable must be associated with an org table"),
+        }
+    }
+
+    /// Returns the plain text of all rows of this table, excluding
+    /// horizontal rules.
+    ///
+    /// Markups are stripped from cell contents, and each row is padded with
+    /// empty strings to `columns_count`.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("| name | qty |\n|------+-----|\n| *apple* | 2 |\n| pear |\n");
+    /// let table = org.tables().next().unwrap();
+    ///
+    /// assert_eq!(table.columns_count(&org), 2);
+    /// assert_eq!(table.header(&org), vec![vec!["name", "qty"]]);
+    /// assert_eq!(table.body(&org), vec![vec!["apple", "2"], vec!["pear", ""]]);
+    /// ```
+    pub fn rows(self, org: &Org) -> Vec<Vec<String>> {
+        let columns = self.columns_count(org);
+        self.data_rows(org)
+            .map(|row_n| {
+                let mut cells: Vec<_> = row_n
+                    .children(&org.arena)
+                    .map(|cell_n| cell_text(org, cell_n))
+                    .collect();
+                cells.resize(columns, String::new());
+                cells
+            })
+            .collect()
+    }
+
+    /// Returns the plain text of header rows of this table, which are
+    /// rows before the first horizontal rule.
+    pub fn header(self, org: &Org) -> Vec<Vec<String>> {
+        let mut rows = self.rows(org);
+        rows.truncate(self.header_rows_count(org));
+        rows
+    }
+
+    /// Returns the plain text of body rows of this table.
+    pub fn body(self, org: &Org) -> Vec<Vec<String>> {
+        let mut rows = self.rows(org);
+        rows.drain(0..self.header_rows_count(org));
+        rows
+    }
+
+    /// Returns the numbers of columns of this table, i.e. the numbers of
+    /// cells in its longest row.
+    pub fn columns_count(self, org: &Org) -> usize {
+        self.data_rows(org)
+            .map(|row_n| row_n.children(&org.arena).count())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn data_rows<'a>(self, org: &'a Org) -> impl Iterator<Item = NodeId> + 'a {
+        self.tbl_n.children(&org.arena).filter(move |&row_n| {
+            matches!(
+                org[row_n],
+                Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body)
+            )
+        })
+    }
+
+    fn header_rows_count(self, org: &Org) -> usize {
+        self.data_rows(org)
+            .take_while(|&row_n| matches!(org[row_n], Element::TableRow(TableRow::Header)))
+            .count()
+    }
+
+    /// Writes this table as comma-separated values, including its header.
+    ///
+    /// Fields containing commas, double quotes or newlines are quoted.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let org = Org::parse("| name | note |\n| apple | red, sweet |\n");
+    ///
+    /// let mut writer = Vec::new();
+    /// org.tables().next().unwrap().write_csv(&org, &mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "name,note\napple,\"red, sweet\"\n"
+    /// );
+    /// ```
+    pub fn write_csv<W: Write>(self, org: &Org, mut w: W) -> Result<(), Error> {
+        for row in self.rows(org) {
+            for (i, field) in row.iter().enumerate() {
+                if i > 0 {
+                    write!(w, ",")?;
+                }
+                if field.contains(&[',', '"', '\n', '\r'][..]) {
+                    write!(w, "\"{}\"", field.replace('"', "\"\""))?;
+                } else {
+                    write!(w, "{}", field)?;
+                }
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Writes this table as tab-separated values, including its header.
+    ///
+    /// Tabs and newlines inside fields are replaced with spaces.
+    pub fn write_tsv<W: Write>(self, org: &Org, mut w: W) -> Result<(), Error> {
+        for row in self.rows(org) {
+            let fields: Vec<_> = row
+                .iter()
+                .map(|field| field.replace(&['\t', '\n', '\r'][..], " "))
+                .collect();
+            writeln!(w, "{}", fields.join("\t"))?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new detached table from comma-separated values.
+    ///
+    /// If `has_header` is true, the first record becomes the header of this
+    /// table. Fields are stored as plain text, with `|` stored as a `\vert{}`
+    /// entity so that it's written back as `|` in csv and html export.
+    ///
+    /// ```rust
+    /// use orgize::{Org, OrgTable};
+    ///
+    /// let mut org = Org::parse("Fruits:\n");
+    /// let table = OrgTable::from_csv("name,qty\napple,2\n\"pear, green\",3\n", true, &mut org);
+    /// let section = org.document().section_node().unwrap();
+    /// section.append(table.table_node(), org.arena_mut());
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "Fruits:\n| name | qty |\n|-\n| apple | 2 |\n| pear, green | 3 |\n"
+    /// );
+    /// ```
+    pub fn from_csv(csv: &str, has_header: bool, org: &mut Org) -> OrgTable {
+        let tbl_n = org.arena.new_node(Element::Table(Table::Org {
+            tblfm: vec![],
+            post_blank: 0,
+            has_header,
+            columns: vec![],
+            special_rows: vec![],
+            affiliated: None,
+        }));
+
+        for (i, record) in parse_csv(csv).into_iter().enumerate() {
+            let header = has_header && i == 0;
+            let (row, cell) = if header {
+                (TableRow::Header, TableCell::Header)
+            } else {
+                (TableRow::Body, TableCell::Body)
+            };
+
+            let row_n = org.arena.new_node(Element::TableRow(row));
+            tbl_n.append(row_n, &mut org.arena);
+
+            for field in record {
+                let cell_n = org.arena.new_node(Element::TableCell(cell.clone()));
+                row_n.append(cell_n, &mut org.arena);
+
+                let value = field.trim().replace(&['\n', '\r'][..], " ");
+                for (j, text) in value.split('|').enumerate() {
+                    if j > 0 {
+                        let vert_n = org.arena.new_node(Element::Entity(Entity {
+                            name: "vert".into(),
+                            brackets: true,
+                        }));
+                        cell_n.append(vert_n, &mut org.arena);
+                    }
+                    if !text.is_empty() {
+                        let text_n = org.arena.new_node(Element::Text {
+                            value: Cow::Owned(text.into()),
+                        });
+                        cell_n.append(text_n, &mut org.arena);
+                    }
+                }
+            }
+
+            if header {
+                let rule_n = org.arena.new_node(Element::TableRow(TableRow::HeaderRule));
+                tbl_n.append(rule_n, &mut org.arena);
+            }
+        }
+
+        OrgTable { tbl_n }
+    }
+
+    /// Deserializes body rows of this table into `T`.
+    ///
+    /// If this table has a header, each row is deserialized as a map keyed
+    /// by the first header row, otherwise as a sequence. Empty fields are
+    /// deserialized as `None`.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Fruit {
+    ///     name: String,
+    ///     qty: u32,
+    ///     note: Option<String>,
+    /// }
+    ///
+    /// let org = Org::parse("| name | qty | note |\n|-\n| apple | 2 | |\n| pear | 3 | ripe |\n");
+    /// let fruits: Vec<Fruit> = org.tables().next().unwrap().deserialize_rows(&org).unwrap();
+    ///
+    /// assert_eq!(fruits[0], Fruit { name: "apple".into(), qty: 2, note: None });
+    /// assert_eq!(fruits[1].note.as_deref(), Some("ripe"));
+    /// ```
+    #[cfg(feature = "ser")]
+    pub fn deserialize_rows<T>(self, org: &Org) -> Result<Vec<T>, serde::de::value::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let header = self.header(org);
+        de::deserialize_rows(header.first().map(Vec::as_slice), &self.body(org))
+    }
+
+    /// Evaluates the formulas of this table and writes the results back
+    /// into its cells.
+    ///
+    /// Only a subset of Org's formula syntax is supported, without Emacs Calc:
+    ///
+    /// + targets: column formulas like `$3=` or `$name=`, and field formulas
+    ///   like `@2$3=`, `@>$2=` or `@I+1$<=`
+    /// + references: `$N`, `$<`, `$>`, `$#`, `$name`, `$+1` and `$-1` for
+    ///   columns; `@N`, `@<`, `@>`, `@#`, `@+1`, `@-1`, `@I` and `@II-1` for
+    ///   rows; and ranges like `@2..@-1` or `@2$1..@>$3`
+    /// + operators: `+`, `-`, `*`, `/`, `^` and parentheses
+    /// + functions: `vsum`, `vmean`, `vmedian`, `vmin`, `vmax`, `vcount`,
+    ///   `vprod`, `min`, `max`, `abs`, `sqrt`, `exp`, `ln`, `log10`, `floor`,
+    ///   `ceil` and `round`
+    /// + format: `;%.Nf`, other formats and flags are ignored
+    ///
+    /// Non-numeric fields are treated as `0` in single references, empty and
+    /// non-numeric fields are skipped in ranges. Column names are defined by
+    /// rows starting with `!`, such rows and rows starting with `^`, `_`, `$`
+    /// or `/` are not affected by column formulas.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    ///
+    /// let mut org = Org::parse("| 2 | 3 |   |\n#+TBLFM: $3=$1*$2\n");
+    /// let table = org.tables().next().unwrap();
+    /// table.evaluate(&mut org).unwrap();
+    ///
+    /// let mut writer = Vec::new();
+    /// org.write_org(&mut writer).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(writer).unwrap(),
+    ///     "| 2 | 3 | 6 |\n#+TBLFM: $3=$1*$2\n"
+    /// );
+    /// ```
+    pub fn evaluate(self, org: &mut Org) -> Result<(), FormulaError> {
+        let tblfm: Vec<String> = self
+            .formulas(org)
+            .iter()
+            .map(|formula| formula.to_string())
+            .collect();
+
+        if tblfm.is_empty() {
+            return Ok(());
+        }
+
+        let (mut grid, rows) = self.grid(org);
+        let old = grid.rows.clone();
+
+        formula::evaluate(&mut grid, &tblfm)?;
+
+        for ((row_n, cells), (new, old)) in rows.into_iter().zip(grid.rows.into_iter().zip(old)) {
+            for (i, value) in new.into_iter().enumerate() {
+                if old.get(i) != Some(&value) {
+                    set_cell(org, row_n, &cells, i, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // collects the contents of data rows, with their rows and cells ids
+    fn grid(self, org: &Org) -> (Grid, Vec<(NodeId, Vec<NodeId>)>) {
+        let mut grid = Grid {
+            rows: vec![],
+            hlines: vec![],
+            header_rows: 0,
+        };
+        let mut rows = vec![];
+
+        for row_n in self.tbl_n.children(&org.arena) {
+            match org[row_n] {
+                Element::TableRow(TableRow::Header) | Element::TableRow(TableRow::Body) => {
+                    let cells: Vec<_> = row_n.children(&org.arena).collect();
+                    grid.rows
+                        .push(cells.iter().map(|&cell| cell_contents(org, cell)).collect());
+                    rows.push((row_n, cells));
+                }
+                Element::TableRow(TableRow::HeaderRule) => {
+                    grid.header_rows = grid.rows.len();
+                    grid.hlines.push(grid.rows.len());
+                }
+                _ => grid.hlines.push(grid.rows.len()),
+            }
+        }
+
+        (grid, rows)
+    }
+}
+
+// returns the org syntax of cell contents
+fn cell_contents(org: &Org, cell: NodeId) -> String {
+    let mut handler = DefaultOrgHandler::default();
+    let mut buf = Vec::new();
+
+    for child in cell.children(&org.arena) {
+        for edge in child.traverse(&org.arena) {
+            // writing to a vector never fails
+            let _ = match edge {
+                NodeEdge::Start(node) => handler.start(&mut buf, &org[node]),
+                NodeEdge::End(node) => handler.end(&mut buf, &org[node]),
+            };
+        }
+    }
+
+    String::from_utf8(buf)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+// returns the plain text of cell contents
+fn cell_text(org: &Org, cell: NodeId) -> String {
+    let mut text = String::new();
+
+    for node in cell.descendants(&org.arena).skip(1) {
+        match &org[node] {
+            Element::Text { value } | Element::Code { value } | Element::Verbatim { value } => {
+                text.push_str(value)
+            }
+            Element::Link(link) => text.push_str(link.desc.as_ref().unwrap_or(&link.path)),
+            Element::RadioLink(radio) => text.push_str(&radio.value),
+            Element::RadioTarget(radio) => text.push_str(&radio.target),
+            Element::Entity(entity) => match entity.definition() {
+                Some(definition) => text.push_str(definition.utf8),
+                None => text.push_str(&entity.name),
+            },
+            // uses org syntax of other objects, e.g. timestamps
+            element if node.children(&org.arena).next().is_none() => {
+                let mut buf = Vec::new();
+                let mut handler = DefaultOrgHandler::default();
+                let _ = handler.start(&mut buf, element);
+                let _ = handler.end(&mut buf, element);
+                text.push_str(&String::from_utf8_lossy(&buf));
+            }
+            _ => (),
+        }
+    }
+
+    text.trim().to_string()
+}
+
+// parses records of comma-separated values
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            _ if quoted => field.push(c),
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+fn set_cell(org: &mut Org, row_n: NodeId, cells: &[NodeId], i: usize, value: String) {
+    let cell_n = match cells.get(i) {
+        Some(&cell_n) => cell_n,
+        None => {
+            // appends missing cells to this row
+            let cell = match org[row_n] {
+                Element::TableRow(TableRow::Header) => TableCell::Header,
+                _ => TableCell::Body,
+            };
+            let mut cell_n = row_n;
+            for _ in row_n.children(&org.arena).count()..=i {
+                cell_n = org.arena.new_node(Element::TableCell(cell.clone()));
+                row_n.append(cell_n, &mut org.arena);
+            }
+            cell_n
+        }
+    };
+
+    let children: Vec<_> = cell_n.children(&org.arena).collect();
+    for child in children {
+        child.remove_subtree(&mut org.arena);
+    }
+
+    if !value.is_empty() {
+        let text = org.arena.new_node(Element::Text {
+            value: Cow::Owned(value),
+        });
+        cell_n.append(text, &mut org.arena);
+    }
+}
+
+impl Org<'_> {
+    /// Returns an iterator of "org" type tables.
+    pub fn tables(&self) -> impl Iterator<Item = OrgTable> + '_ {
+        self.root
+            .descendants(&self.arena)
+            .filter_map(move |node| OrgTable::from_node(node, self))
+    }
+
+    /// Evaluates the formulas of all "org" type tables.
+    pub fn evaluate_tables(&mut self) -> Result<(), FormulaError> {
+        let tables: Vec<_> = self.tables().collect();
+        for table in tables {
+            table.evaluate(self)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn csv() {
+    assert_eq!(
+        parse_csv("a,\"b \"\"c\"\"\"\r\n\"d,\ne\",\n"),
+        vec![vec!["a", "b \"c\""], vec!["d,\ne", ""]]
+    );
+    assert_eq!(parse_csv("a,b"), vec![vec!["a", "b"]]);
+    assert!(parse_csv("").is_empty());
+
+    let csv = "name,note\na|b,\"x, |y|\"\n";
+    let mut org = Org::new();
+    let table = OrgTable::from_csv(csv, true, &mut org);
+    let mut writer = Vec::new();
+    table.write_csv(&org, &mut writer).unwrap();
+    assert_eq!(String::from_utf8(writer).unwrap(), csv);
+}
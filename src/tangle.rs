@@ -0,0 +1,343 @@
+//! Tangling source blocks
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+
+use crate::elements::{Element, HeaderArgs, Tangle};
+use crate::{Headline, Org};
+
+/// A file produced by tangling source blocks, see [`Org::tangle`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TangledFile {
+    /// Contents of the file, source blocks in document order
+    pub contents: String,
+    /// Whether to create missing parent directories, from `:mkdirp`
+    pub mkdirp: bool,
+}
+
+impl TangledFile {
+    /// Writes the contents to `path`, creating its parent directories if
+    /// `mkdirp` is set.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if self.mkdirp {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        fs::write(path, &self.contents)
+    }
+
+    /// Returns `true` if the file at `path` already has the same contents,
+    /// `false` if it differs or doesn't exist.
+    pub fn is_up_to_date<P: AsRef<Path>>(&self, path: P) -> Result<bool, Error> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents == self.contents),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Org<'_> {
+    /// Tangles source blocks into files, like `org-babel-tangle`.
+    ///
+    /// `path` is the path of the org file, relative targets are resolved
+    /// against its directory, and `:tangle yes` writes to a file named
+    /// after it with the extension of the block language.
+    ///
+    /// Header arguments are inherited, see [`Org::header_args`]. Blocks
+    /// under commented headlines are skipped. Blocks of the same file are
+    /// separated by a blank line unless `:padline no`, and wrapped by
+    /// comments linking back to the org file with `:comments link`.
+    ///
+    /// ```rust
+    /// use orgize::Org;
+    /// use std::path::PathBuf;
+    ///
+    /// let org = Org::parse(
+    ///     r#"#+PROPERTY: header-args:rust :tangle src/main.rs :mkdirp yes
+    /// * Main
+    /// #+BEGIN_SRC rust
+    /// use std::env;
+    /// #+END_SRC
+    /// #+BEGIN_SRC rust :comments link
+    /// fn main() {}
+    /// #+END_SRC
+    /// #+BEGIN_SRC python :tangle yes
+    /// print(1)
+    /// #+END_SRC
+    /// "#,
+    /// );
+    ///
+    /// let files = org.tangle("docs/ci.org");
+    ///
+    /// let main = &files[&PathBuf::from("docs/src/main.rs")];
+    /// assert!(main.mkdirp);
+    /// assert_eq!(
+    ///     main.contents,
+    ///     "use std::env;\n\n\
+    ///      // [[file:../ci.org::*Main][Main:2]]\n\
+    ///      fn main() {}\n\
+    ///      // Main:2 ends here\n"
+    /// );
+    /// assert_eq!(files[&PathBuf::from("docs/ci.py")].contents, "print(1)\n");
+    /// ```
+    pub fn tangle<P: AsRef<Path>>(&self, path: P) -> BTreeMap<PathBuf, TangledFile> {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+        let properties = self.document().properties(self);
+        let mut files: BTreeMap<PathBuf, TangledFile> = BTreeMap::new();
+        // number of source blocks under the current headline
+        let mut counter = (None, 0);
+
+        for node in self.root.descendants(&self.arena) {
+            let block = match &self[node] {
+                Element::SourceBlock(block) => block,
+                _ => continue,
+            };
+
+            let headline = node.ancestors(&self.arena).find_map(|n| match self[n] {
+                Element::Headline { level } => Some(Headline::from_node(n, level, self)),
+                _ => None,
+            });
+            let hdl_n = headline.map(Headline::headline_node);
+            counter = if counter.0 == hdl_n {
+                (hdl_n, counter.1 + 1)
+            } else {
+                (hdl_n, 1)
+            };
+
+            let commented =
+                ancestors(headline, self).any(|headline| headline.title(self).is_commented());
+            if commented {
+                continue;
+            }
+
+            let args = self.header_args_with(node, &properties).unwrap_or_default();
+            let (ext, comment_start, comment_end) = language(&block.language);
+            let target = match args.tangle() {
+                Some(Tangle::Yes) => PathBuf::from(format!("{}.{}", stem, ext)),
+                Some(Tangle::File(file)) => PathBuf::from(file),
+                Some(Tangle::No) | None => continue,
+            };
+
+            let file = files.entry(dir.join(&target)).or_default();
+            file.mkdirp |= is_yes(&args, "mkdirp");
+            if !file.contents.is_empty() && args.get("padline") != Some("no") {
+                file.contents.push('\n');
+            }
+
+            let comments = matches!(args.get("comments"), Some("link") | Some("yes"));
+            let description = block
+                .affiliated
+                .as_ref()
+                .and_then(|a| a.name.as_deref())
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| match headline {
+                    Some(headline) => format!("{}:{}", headline.title(self).raw, counter.1),
+                    None => format!("No heading:{}", counter.1),
+                });
+
+            if comments {
+                let link_target = match (&block.affiliated, headline) {
+                    (Some(a), _) if a.name.is_some() => format!("::{}", description),
+                    (_, Some(headline)) => format!("::*{}", headline.title(self).raw),
+                    _ => String::new(),
+                };
+                file.contents += &format!(
+                    "{} [[file:{}{}][{}]]{}\n",
+                    comment_start,
+                    link_path(path, &target),
+                    link_target,
+                    description,
+                    comment_end
+                );
+            }
+
+            let contents = block.unescaped_contents();
+            file.contents += &contents;
+            if !contents.is_empty() && !contents.ends_with('\n') {
+                file.contents.push('\n');
+            }
+
+            if comments {
+                file.contents += &format!(
+                    "{} {} ends here{}\n",
+                    comment_start, description, comment_end
+                );
+            }
+        }
+
+        files
+    }
+}
+
+// headline and its ancestors
+fn ancestors<'a>(headline: Option<Headline>, org: &'a Org) -> impl Iterator<Item = Headline> + 'a {
+    std::iter::successors(headline, move |headline| headline.parent(org))
+}
+
+fn is_yes(args: &HeaderArgs, name: &str) -> bool {
+    matches!(args.get(name), Some("yes") | Some("t"))
+}
+
+// path of the org file relative to the tangled file, `target` is relative
+// to the directory of the org file
+fn link_path(org_path: &Path, target: &Path) -> String {
+    let dir = org_path.parent().unwrap_or_else(|| Path::new(""));
+    let org_path = absolute(org_path);
+    let file = absolute(&dir.join(target));
+    let from: Vec<_> = file.parent().unwrap_or(&file).components().collect();
+    let to: Vec<_> = org_path.components().collect();
+
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    // no common root, e.g. different drives on Windows
+    if common == 0 {
+        return org_path.to_string_lossy().into();
+    }
+
+    let mut path = "../".repeat(from.len() - common);
+    path += &to[common..]
+        .iter()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    path
+}
+
+// absolute path with `.` and `..` resolved lexically
+fn absolute(path: &Path) -> PathBuf {
+    let mut absolute = PathBuf::new();
+    if path.is_relative() {
+        absolute.extend(env::current_dir().unwrap_or_default().components());
+    }
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if matches!(
+                    absolute.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    absolute.pop();
+                }
+            }
+            component => absolute.push(component),
+        }
+    }
+    absolute
+}
+
+// file extension and comment delimiters of a language
+fn language(language: &str) -> (&str, &'static str, &'static str) {
+    let (ext, start, end) = match language {
+        "emacs-lisp" | "elisp" => ("el", ";;", ""),
+        "lisp" => ("lisp", ";;", ""),
+        "scheme" => ("scm", ";;", ""),
+        "clojure" => ("clj", ";;", ""),
+        "sh" | "bash" | "shell" => ("sh", "#", ""),
+        "python" => ("py", "#", ""),
+        "ruby" => ("rb", "#", ""),
+        "perl" => ("pl", "#", ""),
+        "makefile" => ("mk", "#", ""),
+        "R" | "yaml" | "toml" | "conf" | "nix" | "zsh" | "fish" => (language, "#", ""),
+        "C" => ("c", "//", ""),
+        "cpp" | "C++" => ("cpp", "//", ""),
+        "rust" => ("rs", "//", ""),
+        "js" | "javascript" => ("js", "//", ""),
+        "typescript" => ("ts", "//", ""),
+        "kotlin" => ("kt", "//", ""),
+        "java" | "go" | "swift" | "scala" => (language, "//", ""),
+        "haskell" => ("hs", "--", ""),
+        "lua" | "sql" => (language, "--", ""),
+        "latex" => ("tex", "%", ""),
+        "matlab" => ("m", "%", ""),
+        "erlang" => ("erl", "%", ""),
+        "css" => ("css", "/*", " */"),
+        "html" | "xml" => (language, "<!--", " -->"),
+        _ => (language, "#", ""),
+    };
+    (ext, start, end)
+}
+
+#[test]
+fn tangle() {
+    let org = Org::parse(
+        r#"#+BEGIN_SRC rust :tangle yes
+fn main() {}
+#+END_SRC
+* COMMENT Skipped
+#+BEGIN_SRC rust :tangle yes
+skipped
+#+END_SRC
+* Lib
+:PROPERTIES:
+:header-args: :tangle src/lib.rs :comments link :padline no
+:END:
+#+NAME: first
+#+BEGIN_SRC rust
+,* not a headline
+#+END_SRC
+#+BEGIN_SRC rust :tangle no
+not tangled
+#+END_SRC
+#+BEGIN_SRC css :tangle style.css :comments link
+a {}
+#+END_SRC
+#+BEGIN_SRC rust
+last
+#+END_SRC
+"#,
+    );
+
+    let files = org.tangle("/repo/main.org");
+    let files: Vec<_> = files
+        .iter()
+        .map(|(path, file)| (path.to_str().unwrap(), &*file.contents))
+        .collect();
+
+    assert_eq!(
+        files,
+        vec![
+            ("/repo/main.rs", "fn main() {}\n"),
+            (
+                "/repo/src/lib.rs",
+                "// [[file:../main.org::first][first]]\n\
+                 * not a headline\n\
+                 // first ends here\n\
+                 // [[file:../main.org::*Lib][Lib:4]]\n\
+                 last\n\
+                 // Lib:4 ends here\n"
+            ),
+            (
+                "/repo/style.css",
+                "/* [[file:main.org::*Lib][Lib:3]] */\na {}\n/* Lib:3 ends here */\n"
+            ),
+        ]
+    );
+
+    assert_eq!(
+        link_path(Path::new("a/b.org"), Path::new("../x.rs")),
+        "a/b.org"
+    );
+    assert_eq!(
+        link_path(
+            Path::new("/repo/docs/main.org"),
+            Path::new("/repo/src/lib.rs")
+        ),
+        "../docs/main.org"
+    );
+    assert_eq!(
+        link_path(
+            Path::new("/repo/docs/main.org"),
+            Path::new("../../out/./x.rs")
+        ),
+        "../repo/docs/main.org"
+    );
+}
@@ -106,6 +106,13 @@ impl Org<'_> {
                         expect_element!(child, "Headline", Element::Headline { .. });
                     }
                 }
+                Element::Inlinetask(_) => {
+                    expect_children!(node_id);
+
+                    if let Some(child) = node_id.children(&self.arena).next() {
+                        expect_element!(child, "Title", Element::Title(_));
+                    }
+                }
                 Element::Title(title) => {
                     if !title.raw.is_empty() && node.first_child().is_none() {
                         errors.push(ValidationError::ExpectedChildren { at: node_id });
@@ -151,7 +158,9 @@ impl Org<'_> {
                 | Element::InlineCall(_)
                 | Element::Link(_)
                 | Element::Macros(_)
-                | Element::RadioTarget
+                | Element::LineBreak
+                | Element::RadioTarget(_)
+                | Element::RadioLink(_)
                 | Element::Snippet(_)
                 | Element::Target(_)
                 | Element::Text { .. }
@@ -159,8 +168,13 @@ impl Org<'_> {
                 | Element::Verbatim { .. }
                 | Element::FnDef(_)
                 | Element::Clock(_)
+                | Element::Citation(_)
                 | Element::Comment { .. }
+                | Element::DiarySexp(_)
                 | Element::FixedWidth { .. }
+                | Element::LatexEnvironment(_)
+                | Element::Entity(_)
+                | Element::LatexFragment(_)
                 | Element::Keyword(_)
                 | Element::Rule(_)
                 | Element::Cookie(_)
@@ -180,6 +194,8 @@ impl Org<'_> {
                 | Element::Italic
                 | Element::Underline
                 | Element::Strike
+                | Element::Subscript { .. }
+                | Element::Superscript { .. }
                 | Element::DynBlock(_) => {
                     expect_children!(node_id);
                 }
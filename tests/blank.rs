@@ -11,27 +11,52 @@ CONTENTS
 #+END_QUOTE
 
 * Headline 1
-SCHEDULED: <2019-10-28 Mon>
+SCHEDULED: <2019-10-28 Mon .+1d/3d> DEADLINE: <2019-10-30 Wed +1w --2d>
 :PROPERTIES:
 :ID: headline-1
 :END:
 
+#+NAME: drawer
 :LOGBOOK:
 
 CLOCK: [2019-10-28 Mon 08:53]
 
 CLOCK: [2019-10-28 Mon 08:53]--[2019-10-28 Mon 08:53] => 0:00
+CLOCK: [2019-10-28 Mon 08:53-09:53] => 1:00
 
 :END:
 
+#+NAME: rule
 -----
 
+| <l> |  | <r10> |
+| / | < | > |
+| a | b | 1 |
+|-
+|  | <c> |  |
+| c | d | 2 |
+#+TBLFM: $3=$2
+#+TBLFM: @2$1=x
+
+\alpha{} \rarr $x$ \(y\) \ref{z} H_2O x^{2} https://a.org <mailto:b@c>
+<<<radio>>> Radio\\  
+[cite/t:see ;@doe2020 p. 3;@smith;  and others] line break <2024-03-01 Fri 10:00-11:30 +1w> [2024-03-01 Fri 10:00-11:30] [2024-01-01 Mon -1w ++2m]
+
+*************** TODO Inlinetask
+
+CONTENTS
+
+*************** END
+
 #+CALL: VALUE
 
 #
 # Comment
 #
 
+%%(diary-anniversary 10 31 1948) Birthday
+
+#+NAME: dyn
 #+BEGIN: NAME PARAMETERS
 
 CONTENTS
@@ -48,22 +73,36 @@ COMMENT
 
 #+END_COMMENT
 
+#+attr_html: :class example
+#+TBLNAME: example
+#+caption[Short]: Caption
 #+BEGIN_EXAMPLE
 #+END_EXAMPLE
 
+\begin{align*}
+
+x &= 1
+
+\end{align*}
+
+#+ATTR_HTML: :class foo
     1. 1
 
 2. 2
 
     3. 3
 
-        + 1
+        + [X] 1
 
-        + 2
+        + [@2] [ ] 2
 
-            - 3
+            - term :: 3
 
-            - 4
+            - [-] term :: 4
+
+            - [x] term  ::  4.5
+
+            - empty ::
 
         + 5
 
@@ -82,3 +121,25 @@ fn blank() {
 
     assert_eq!(String::from_utf8(writer).unwrap(), ORG_STR);
 }
+
+#[test]
+fn document_properties() {
+    let org_str = ":PROPERTIES:\n:ID: 2c9d\n:TAGS+:\n:END:\n\n#+TITLE: org\n";
+    let org = Org::parse(org_str);
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+
+    assert_eq!(String::from_utf8(writer).unwrap(), org_str);
+}
+
+#[test]
+fn document_properties_blank_lines() {
+    let org_str = "\n:PROPERTIES:\n:ID: x\n:END:\n* h\n";
+    let org = Org::parse(org_str);
+
+    let mut writer = Vec::new();
+    org.write_org(&mut writer).unwrap();
+
+    assert_eq!(String::from_utf8(writer).unwrap(), org_str);
+}
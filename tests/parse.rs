@@ -170,3 +170,366 @@ test_suite!(
      <tbody><tr></tr></tbody>\
      </table></section></main>"
 );
+
+test_suite!(
+    table_cookies_and_formulas,
+    r#"
+|      | <r> | <c5> |
+| / | < | > |
+|    a |   1 |    2 |
+#+TBLFM: $2=$1
+#+tblfm: $3=$2*2
+"#,
+    "<main><section><table><tbody>\
+     <tr><td>a</td><td class=\"org-right\">1</td><td class=\"org-center\">2</td></tr>\
+     </tbody></table></section></main>"
+);
+
+test_suite!(
+    table_el,
+    r#"
+#+CAPTION: Spans
++-----+-----+-----+
+| a   | b         |
++=====+=====+=====+
+| c   | d   | e   |
+|     +-----+     |
+|     | f < |     |
++-----+-----+-----+
+"#,
+    "<main><section><table><caption>Spans</caption>\
+     <tr><th>a</th><th colspan=\"2\">b</th></tr>\
+     <tr><td rowspan=\"2\">c</td><td>d</td><td rowspan=\"2\">e</td></tr>\
+     <tr><td>f &lt;</td></tr>\
+     </table></section></main>"
+);
+
+test_suite!(
+    diary_sexp,
+    "Holidays\n%%(diary-float t 4 2) Thanksgiving\n  %%(indented)",
+    "<main><section><p>Holidays</p><p>  %%(indented)</p></section></main>"
+);
+
+test_suite!(
+    citations,
+    r#"As shown [cite:see ;@doe2020 p. 3; @smith & co] and [cite/t:@doe2020].
+[cite/nocite:@hidden]
+
+#+print_bibliography:
+"#,
+    "<main><section><p>As shown <span class=\"citation\">(see <a href=\"#cite-doe2020\">doe2020</a> p. 3; \
+     <a href=\"#cite-smith\">smith</a> &amp; co)</span> and <span class=\"citation\">\
+     <a href=\"#cite-doe2020\">doe2020</a></span>.\n</p>\
+     <div class=\"bibliography\"><ul><li id=\"cite-doe2020\">doe2020</li>\
+     <li id=\"cite-smith\">smith</li><li id=\"cite-hidden\">hidden</li></ul></div>\
+     </section></main>"
+);
+
+test_suite!(
+    affiliated_keywords,
+    r#"
+#+NAME: tbl
+#+CAPTION: Numbers
+#+ATTR_HTML: :class data
+| 0 | 1 |
+
+#+CAPTION: A cat
+[[./cat.png]]
+
+#+ATTR_HTML: :class foo
+- item
+
+#+NAME: line
+-----
+
+#+NAME: orphan
+"#,
+    "<main><section><table id=\"tbl\" class=\"data\"><caption>Numbers</caption>\
+     <tbody><tr><td>0</td><td>1</td></tr></tbody></table>\
+     <figure><p><a href=\"./cat.png\">./cat.png</a></p><figcaption>A cat</figcaption></figure>\
+     <ul class=\"foo\"><li><p>item</p></li></ul><hr id=\"line\">\
+     </section></main>"
+);
+
+test_suite!(
+    list_items,
+    r#"
+- [X] done
+- [-] partial
+- [ ] todo
+text
+3. [@3] three
+4. four
+text
+- term :: /description/
+- [ ] other :: text
+"#,
+    "<main><section>\
+     <ul>\
+     <li class=\"on\"><input type=\"checkbox\" checked disabled> <p>done</p></li>\
+     <li class=\"trans\"><input type=\"checkbox\" disabled> <p>partial</p></li>\
+     <li class=\"off\"><input type=\"checkbox\" disabled> <p>todo</p></li>\
+     </ul>\
+     <p>text</p>\
+     <ol><li value=\"3\"><p>three</p></li><li><p>four</p></li></ol>\
+     <p>text</p>\
+     <dl>\
+     <dt>term</dt><dd><p><i>description</i></p></dd>\
+     <dt class=\"off\"><input type=\"checkbox\" disabled> other</dt><dd><p>text</p></dd>\
+     </dl>\
+     </section></main>"
+);
+
+test_suite!(
+    list_indentation,
+    "- one\n  continued\n  #+BEGIN_EXAMPLE\ntext\n  #+END_EXAMPLE\n\t- nested\n\n        - nested2\n- two\n\n\n- three\n",
+    "<main><section>\
+     <ul><li><p>one\n  continued</p><pre class=\"example\">text\n</pre>\
+     <ul><li><p>nested</p></li><li><p>nested2</p></li></ul></li>\
+     <li><p>two</p></li></ul>\
+     <ul><li><p>three</p></li></ul>\
+     </section></main>"
+);
+
+test_suite!(
+    latex_environment,
+    r#"
+#+NAME: eq
+\begin{equation}
+a < b & c
+\end{equation}
+\begin{foo
+"#,
+    "<main><section>\
+     <div class=\"latex-environment\" id=\"eq\">\\begin{equation}\na &lt; b &amp; c\n\\end{equation}</div>\
+     <p>\\begin{foo</p>\
+     </section></main>"
+);
+
+test_suite!(
+    entities_and_latex_fragments,
+    r#"
+\alpha \rarr{}b, \nbsp \alphax
+$x^2$ and \(a < b\), $$1$$ \[ c \] \ref{eq} $5 and $10
+"#,
+    "<main><section><p>&alpha; &rarr;b, &nbsp; \\alphax\n\
+     \\(x^2\\) and \\(a &lt; b\\), \\[1\\] \\[ c \\] \\ref{eq} $5 and $10</p>\
+     </section></main>"
+);
+
+test_suite!(
+    sub_superscripts,
+    "H_2O x^{2 + y} _underline_ a_{b_c} e^-1, x^*\n",
+    "<main><section><p>H<sub>2O</sub> x<sup>2 + y</sup> <u>underline</u> \
+     a<sub>b<sub>c</sub></sub> e<sup>-1</sup>, x<sup>*</sup></p></section></main>"
+);
+
+#[test]
+fn sub_superscripts_braces() {
+    use orgize::{ParseConfig, UseSubSuperscripts};
+
+    let org = Org::parse_custom(
+        "H_2O x^{2} y^{}",
+        &ParseConfig {
+            use_sub_superscripts: UseSubSuperscripts::Braces,
+            ..Default::default()
+        },
+    );
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section><p>H_2O x<sup>2</sup> y^{}</p></section></main>"
+    );
+
+    let org = Org::parse_custom(
+        "H_2O x^{2}",
+        &ParseConfig {
+            use_sub_superscripts: UseSubSuperscripts::Never,
+            ..Default::default()
+        },
+    );
+    let mut writer = Vec::new();
+    org.write_html(&mut writer).unwrap();
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section><p>H_2O x^{2}</p></section></main>"
+    );
+}
+
+test_suite!(
+    plain_and_angle_links,
+    "See https://example.com/a_b, <mailto:foo@bar> and (doi:10.1000/182).\nfoohttps://x <unknown:x>\n",
+    "<main><section><p>See <a href=\"https://example.com/a_b\">https://example.com/a_b</a>, \
+     <a href=\"mailto:foo@bar\">mailto:foo@bar</a> and \
+     (<a href=\"doi:10.1000/182\">doi:10.1000/182</a>).\n\
+     foohttps://x &lt;unknown:x&gt;</p></section></main>"
+);
+
+test_suite!(
+    radio_links,
+    "* <<<Org Mode>>>\nAbout org  MODE and *org mode*, not org modes.\n",
+    "<main><h1><a id=\"radio-org-mode\">Org Mode</a></h1><section>\
+     <p>About <a href=\"#radio-org-mode\">org  MODE</a> and \
+     <b><a href=\"#radio-org-mode\">org mode</a></b>, not org modes.</p></section></main>"
+);
+
+test_suite!(
+    line_breaks,
+    "Name\\\\\n1 Street\\\\  \nTown \\\\\\\nnot \\\\ here\n",
+    "<main><section><p>Name<br>\n1 Street<br>  \nTown \\\\\\\nnot \\\\ here</p></section></main>"
+);
+
+test_suite!(
+    block_switches,
+    r#"
+#+BEGIN_SRC emacs-lisp -n 9 -r
+  (save-excursion       (ref:sc)
+     (goto-char (point-min)))
+  ,* not a headline
+#+END_SRC
+
+#+BEGIN_EXAMPLE -i
+  ,#+KEYWORD (ref:kw)
+#+END_EXAMPLE
+
+See [[(sc)]].
+"#,
+    "<main><section>\
+     <div class=\"org-src-container\"><pre class=\"src src-emacs-lisp\">\
+     <span class=\"linenr\"> 9: </span><span id=\"coderef-sc\" class=\"coderef-off\">(save-excursion</span>\n\
+     <span class=\"linenr\">10: </span>   (goto-char (point-min)))\n\
+     <span class=\"linenr\">11: </span>* not a headline\n</pre></div>\
+     <pre class=\"example\"><span id=\"coderef-kw\" class=\"coderef-off\">  #+KEYWORD (kw)</span>\n</pre>\
+     <p>See <a href=\"#coderef-sc\" class=\"coderef\">(sc)</a>.</p></section></main>"
+);
+
+test_suite!(
+    continued_line_numbers,
+    r#"#+BEGIN_EXAMPLE -n
+a
+b
+#+END_EXAMPLE
+#+BEGIN_SRC sh
+unnumbered
+#+END_SRC
+#+BEGIN_SRC sh +n
+c
+#+END_SRC
+#+BEGIN_EXAMPLE +n 10
+d
+#+END_EXAMPLE
+"#,
+    "<main><section>\
+     <pre class=\"example\"><span class=\"linenr\">1: </span>a\n<span class=\"linenr\">2: </span>b\n</pre>\
+     <div class=\"org-src-container\"><pre class=\"src src-sh\">unnumbered\n</pre></div>\
+     <div class=\"org-src-container\"><pre class=\"src src-sh\"><span class=\"linenr\">3: </span>c\n</pre></div>\
+     <pre class=\"example\"><span class=\"linenr\">13: </span>d\n</pre>\
+     </section></main>"
+);
+
+test_suite!(
+    time_ranges,
+    "<2024-03-01 Fri 10:00-11:30> [2024-03-01 Fri 10:00-11:30 +1w]\n",
+    "<main><section><p>\
+     <span class=\"timestamp-wrapper\"><span class=\"timestamp\">&lt;2024-03-01 Fri 10:00-11:30&gt;</span></span> \
+     <span class=\"timestamp-wrapper\"><span class=\"timestamp\">[2024-03-01 Fri 10:00-11:30 +1w]</span></span>\
+     </p></section></main>"
+);
+
+test_suite!(
+    inlinetask,
+    r#"
+* title
+*************** TODO task /one/
+body
+*************** END
+*************** task two
+section
+"#,
+    "<main><h1>title</h1><section>\
+     <div class=\"inlinetask\"><h6>task <i>one</i></h6><p>body</p></div>\
+     <div class=\"inlinetask\"><h6>task two</h6></div>\
+     <p>section</p></section></main>"
+);
+
+test_suite!(
+    exports,
+    r#"#+BEGIN_SRC sh :exports code
+echo 1
+#+END_SRC
+
+#+RESULTS:
+: 1
+
+#+NAME: two
+#+BEGIN_SRC sh :exports results
+echo 2
+#+END_SRC
+
+#+BEGIN_SRC sh :exports none
+echo 3
+#+END_SRC
+
+#+RESULTS:
+: 3
+
+#+BEGIN_SRC sh
+echo 4
+#+END_SRC
+
+#+RESULTS: two
+: 2
+
+#+CALL: two() :exports code
+
+#+RESULTS:
+:results:
+2
+:end:
+
+#+CALL: two() :exports none
+
+#+RESULTS: two()
+: 2
+
+#+BEGIN_SRC sh
+echo 5
+#+END_SRC
+
+#+RESULTS:
+: 5
+"#,
+    "<main><section><div class=\"org-src-container\"><pre class=\"src src-sh\">echo 1\n</pre></div>\
+     <div class=\"org-src-container\"><pre class=\"src src-sh\">echo 4\n</pre></div>\
+     <pre class=\"example\">: 2\n</pre>\
+     <div class=\"org-src-container\"><pre class=\"src src-sh\">echo 5\n</pre></div></section></main>"
+);
+
+#[test]
+fn exports_disabled() {
+    use orgize::export::DefaultHtmlHandler;
+
+    let mut handler = DefaultHtmlHandler::default();
+    handler.apply_exports = false;
+
+    let mut writer = Vec::new();
+    Org::parse("#+BEGIN_SRC sh :exports none\necho 1\n#+END_SRC\n")
+        .write_html_custom(&mut writer, &mut handler)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer).unwrap(),
+        "<main><section><div class=\"org-src-container\"><pre class=\"src src-sh\">echo 1\n</pre></div>\
+         </section></main>"
+    );
+}
+
+test_suite!(
+    mixed_description_list,
+    "- a :: b\n- c\n",
+    "<main><section><dl>\
+     <dt>a</dt><dd><p>b</p></dd>\
+     <dt>(no term)</dt><dd><p>c</p></dd>\
+     </dl></section></main>"
+);